@@ -0,0 +1,102 @@
+use std::process::ExitCode;
+
+use ziplocator_data::{
+    Dataset, DatasetError, DatasetLoader, DatasetSource, SourceFormat, ValidationPolicy, ZipChange,
+};
+
+const USAGE: &str = "Usage: diff [--format <format>] <old> [--format <format>] <new>
+            [--threshold-km <km>] [--output csv|json]
+            [--invalid-rows drop|keep|error]
+
+<old> and <new> are either a known source name or the path to an extracted
+file, which is read without checksum verification. --format sets the format
+of the path following it: simplemaps (default), census or geonames.";
+
+fn main() -> ExitCode {
+    let mut datasets = Vec::new();
+    let mut format = SourceFormat::SIMPLEMAPS;
+    let mut threshold_km = 1.0;
+    let mut json = false;
+    let mut validation = ValidationPolicy::default();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--threshold-km" => match args.next().and_then(|km| km.parse().ok()) {
+                Some(km) => threshold_km = km,
+                None => return usage(),
+            },
+            "--output" => match args.next().as_deref() {
+                Some("csv") => json = false,
+                Some("json") => json = true,
+                _ => return usage(),
+            },
+            "--invalid-rows" => match args.next().and_then(|policy| policy.parse().ok()) {
+                Some(policy) => validation = policy,
+                None => return usage(),
+            },
+            "--format" => match args.next().and_then(|format| format.parse().ok()) {
+                Some(next) => format = next,
+                None => return usage(),
+            },
+            _ => datasets.push((
+                arg,
+                std::mem::replace(&mut format, SourceFormat::SIMPLEMAPS),
+            )),
+        }
+    }
+
+    let [old, new] = datasets.as_slice() else {
+        return usage();
+    };
+
+    let load = |dataset| load(dataset, validation);
+    let (old, new) = match load(old).and_then(|old| Ok((old, load(new)?))) {
+        Ok(datasets) => datasets,
+        Err(error) => {
+            eprintln!("Loading dataset failed: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let diff = old.diff(&new, threshold_km);
+    eprintln!(
+        "{} added, {} removed, {} relocated by more than {threshold_km} km",
+        diff.count(ZipChange::Added),
+        diff.count(ZipChange::Removed),
+        diff.count(ZipChange::Relocated)
+    );
+
+    let stdout = std::io::stdout().lock();
+    let written = if json {
+        diff.write_json(stdout)
+    } else {
+        diff.write_csv(stdout)
+    };
+
+    if let Err(error) = written {
+        eprintln!("Writing diff failed: {error}");
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn load(
+    (name, format): &(String, SourceFormat),
+    validation: ValidationPolicy,
+) -> Result<Dataset, DatasetError> {
+    let loader = match DatasetSource::find(name) {
+        Some(source) => DatasetLoader::new().source(source).download(),
+        None => DatasetLoader::new().path(name).format(*format),
+    };
+
+    let (dataset, report) = loader.validation(validation).load_with_report()?;
+    eprintln!("{name} validation: {report}");
+    Ok(dataset)
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::FAILURE
+}
@@ -0,0 +1,36 @@
+use ziplocator_data::{DatasetLoader, ExportFormat, ValidationPolicy};
+
+const USAGE: &str = "Usage: export geojson|kml|csv [--invalid-rows drop|keep|error] > <file>";
+
+fn main() {
+    let args: Vec<_> = std::env::args().skip(1).collect();
+    let (format, validation) = match args.as_slice() {
+        [format] => (format.parse(), Ok(ValidationPolicy::default())),
+        [format, flag, policy] if flag == "--invalid-rows" => (format.parse(), policy.parse()),
+        _ => {
+            eprintln!("{USAGE}");
+            std::process::exit(2);
+        }
+    };
+    let (format, validation): (ExportFormat, _) = match (format, validation) {
+        (Ok(format), Ok(validation)) => (format, validation),
+        (Err(error), _) | (_, Err(error)) => {
+            eprintln!("{error}\n\n{USAGE}");
+            std::process::exit(2);
+        }
+    };
+
+    let (dataset, report) = DatasetLoader::new()
+        .validation(validation)
+        .load_with_report()
+        .unwrap_or_else(|error| {
+            eprintln!("Loading dataset failed: {error}");
+            std::process::exit(1);
+        });
+    eprintln!("Dataset validation: {report}");
+
+    if let Err(error) = dataset.exporter().write(format, std::io::stdout().lock()) {
+        eprintln!("Exporting dataset failed: {error}");
+        std::process::exit(1);
+    }
+}
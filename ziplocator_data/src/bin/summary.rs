@@ -0,0 +1,32 @@
+use ziplocator_data::{DatasetLoader, ValidationPolicy};
+
+const USAGE: &str = "Usage: summary [--invalid-rows drop|keep|error]";
+
+fn main() {
+    let validation = match std::env::args().skip(1).collect::<Vec<_>>().as_slice() {
+        [] => ValidationPolicy::default(),
+        [flag, policy] if flag == "--invalid-rows" => policy.parse().unwrap_or_else(|error| {
+            eprintln!("{error}\n\n{USAGE}");
+            std::process::exit(2);
+        }),
+        _ => {
+            eprintln!("{USAGE}");
+            std::process::exit(2);
+        }
+    };
+
+    let loaded = DatasetLoader::new()
+        .validation(validation)
+        .load_with_report();
+    let summary = loaded.and_then(|(dataset, report)| {
+        eprintln!("Dataset validation: {report}");
+        dataset.summary()
+    });
+    match summary {
+        Ok(summary) => println!("{summary}"),
+        Err(error) => {
+            eprintln!("Loading dataset failed: {error}");
+            std::process::exit(1);
+        }
+    }
+}
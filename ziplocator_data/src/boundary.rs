@@ -0,0 +1,191 @@
+use crate::{geo, Dataset, DatasetError, ZipCode};
+use rstar::{primitives::GeomWithData, RTree, AABB};
+use std::{path::Path, sync::Arc};
+
+pub const BOUNDARIES_ENV: &str = "ZIPLOCATOR_BOUNDARIES";
+
+/// Feature properties holding the zip in Census ZCTA boundary files
+const ZIP_PROPERTIES: &[&str] = &[
+    "ZCTA5CE20",
+    "ZCTA5CE10",
+    "GEOID20",
+    "GEOID10",
+    "ZCTA5",
+    "GEOID",
+];
+
+type IndexedShape = GeomWithData<rstar::primitives::Rectangle<[f64; 2]>, usize>;
+
+/// ZCTA boundary polygons with an R-tree over their bounding boxes.
+#[derive(Debug)]
+pub struct ZipBoundaries {
+    index: RTree<IndexedShape>,
+    shapes: Vec<ZipShape>,
+}
+
+/// All rings of a zip's (multi)polygon as `(lat, lon)` vertices. Holes and
+/// separate parts are handled alike by the even-odd rule.
+#[derive(Debug)]
+struct ZipShape {
+    zip: ZipCode,
+    rings: Vec<Vec<(f64, f64)>>,
+}
+
+impl ZipBoundaries {
+    /// Reads a shapefile if the extension is `.shp`, GeoJSON otherwise.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, DatasetError> {
+        let path = path.as_ref();
+        if path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("shp"))
+        {
+            Self::from_shapefile(path)
+        } else {
+            Self::from_geojson(path)
+        }
+    }
+
+    pub fn from_geojson(path: impl AsRef<Path>) -> Result<Self, DatasetError> {
+        let geojson: geojson::GeoJson = std::fs::read_to_string(path)?.parse()?;
+        let features = geojson::FeatureCollection::try_from(geojson)?;
+
+        let shapes = features.features.into_iter().filter_map(|feature| {
+            let zip =
+                ZIP_PROPERTIES
+                    .iter()
+                    .find_map(|property| match feature.property(property)? {
+                        serde_json::Value::String(zip) => zip.parse().ok(),
+                        serde_json::Value::Number(zip) => ZipCode::new(zip.as_u64()? as u32).ok(),
+                        _ => None,
+                    })?;
+
+            let polygons = match feature.geometry?.value {
+                geojson::Value::Polygon(polygon) => vec![polygon],
+                geojson::Value::MultiPolygon(polygons) => polygons,
+                _ => return None,
+            };
+            let rings = polygons
+                .into_iter()
+                .flatten()
+                .map(|ring| {
+                    ring.into_iter()
+                        .filter_map(|position| Some((*position.get(1)?, *position.first()?)))
+                        .collect()
+                })
+                .collect();
+
+            Some(ZipShape { zip, rings })
+        });
+
+        Ok(Self::new(shapes.collect()))
+    }
+
+    /// Reads a shapefile together with the `.dbf` next to it.
+    pub fn from_shapefile(path: impl AsRef<Path>) -> Result<Self, DatasetError> {
+        let mut reader = shapefile::Reader::from_path(path)?;
+
+        let mut shapes = Vec::new();
+        for shape_record in reader.iter_shapes_and_records() {
+            let (shape, record) = shape_record?;
+
+            let zip = ZIP_PROPERTIES
+                .iter()
+                .find_map(|property| match record.get(property)? {
+                    shapefile::dbase::FieldValue::Character(Some(zip)) => zip.parse().ok(),
+                    _ => None,
+                });
+            let (Some(zip), shapefile::Shape::Polygon(polygon)) = (zip, shape) else {
+                continue;
+            };
+
+            let rings = polygon
+                .rings()
+                .iter()
+                .map(|ring| {
+                    ring.points()
+                        .iter()
+                        .map(|point| (point.y, point.x))
+                        .collect()
+                })
+                .collect();
+            shapes.push(ZipShape { zip, rings });
+        }
+
+        Ok(Self::new(shapes))
+    }
+
+    fn new(shapes: Vec<ZipShape>) -> Self {
+        let envelopes = shapes
+            .iter()
+            .enumerate()
+            .filter_map(|(shape, ZipShape { rings, .. })| {
+                let points: Vec<_> = rings
+                    .iter()
+                    .flatten()
+                    .map(|&(lat, lon)| [lat, lon])
+                    .collect();
+                if points.is_empty() {
+                    return None;
+                }
+
+                let envelope = AABB::from_points(&points);
+                let rectangle =
+                    rstar::primitives::Rectangle::from_corners(envelope.lower(), envelope.upper());
+                Some(IndexedShape::new(rectangle, shape))
+            })
+            .collect();
+
+        Self {
+            index: RTree::bulk_load(envelopes),
+            shapes,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    /// The zip whose polygon contains the point, if any
+    pub fn zip_at(&self, lat: f64, lon: f64) -> Option<ZipCode> {
+        self.index
+            .locate_all_at_point(&[lat, lon])
+            .map(|candidate| &self.shapes[candidate.data])
+            .find(|shape| {
+                shape
+                    .rings
+                    .iter()
+                    .filter(|ring| geo::polygon_contains(ring, lat, lon))
+                    .count()
+                    % 2
+                    == 1
+            })
+            .map(|shape| shape.zip)
+    }
+}
+
+impl Dataset {
+    pub fn with_boundaries(mut self, boundaries: ZipBoundaries) -> Self {
+        self.boundaries = Some(Arc::new(boundaries));
+        self
+    }
+
+    pub fn boundaries(&self) -> Option<&ZipBoundaries> {
+        self.boundaries.as_deref()
+    }
+
+    /// The zip whose boundary contains the point, falling back to the nearest
+    /// centroid when no boundaries are loaded or the point is outside all of
+    /// them. Boundaries of zips without a row, e.g. filtered out ones or
+    /// ZCTAs missing from the source, are ignored.
+    pub fn zip_at(&self, lat: f64, lon: f64) -> Option<ZipCode> {
+        self.boundaries
+            .as_ref()
+            .and_then(|boundaries| boundaries.zip_at(lat, lon))
+            .filter(|&zip| self.zip_row(zip).is_some())
+            .or_else(|| self.nearest_zip(lat, lon))
+    }
+}
@@ -0,0 +1,28 @@
+use crate::DatasetError;
+use polars::prelude::*;
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+/// Bump whenever the cleaned dataframe layout changes, so stale caches are
+/// ignored instead of loaded.
+const FORMAT_VERSION: u32 = 1;
+
+/// The parsed dataframe is cached next to the raw file it was parsed from,
+/// which `data_downloader` already names after its SHA-256 hash.
+pub(crate) fn parquet_path(source_path: &Path) -> PathBuf {
+    source_path.with_extension(format!("v{FORMAT_VERSION}.parquet"))
+}
+
+pub(crate) fn read_parquet(path: &Path) -> Result<DataFrame, DatasetError> {
+    Ok(ParquetReader::new(File::open(path)?).finish()?)
+}
+
+pub(crate) fn write_parquet(path: &Path, dataframe: &DataFrame) -> Result<(), DatasetError> {
+    let partial_path = path.with_extension("parquet.partial");
+    ParquetWriter::new(File::create(&partial_path)?).finish(&mut dataframe.clone())?;
+    std::fs::rename(partial_path, path)?;
+
+    Ok(())
+}
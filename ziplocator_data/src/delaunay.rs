@@ -0,0 +1,152 @@
+use crate::{geo, projection, Dataset, ZipCode};
+use spade::{handles::FixedVertexHandle, DelaunayTriangulation, Point2, Triangulation as _};
+use std::collections::HashMap;
+
+/// Zips further apart than this are not neighbours even if they share a
+/// Delaunay edge, e.g. across the ocean between Hawaii and the mainland
+pub const MAX_NEIGHBOR_DISTANCE_KM: f64 = 500.0;
+
+/// Delaunay triangulation of the zip centroids in Web Mercator, which keeps
+/// angles and so the empty circle test true to the ground even in Alaska.
+/// Longitudes east of the antimeridian are moved west of it, so Guam and the
+/// western Aleutians sit next to the rest of the US. Zips sharing a centroid
+/// share a vertex.
+pub struct ZipTriangulation {
+    triangulation: DelaunayTriangulation<Point2<f64>>,
+    vertices: HashMap<ZipCode, FixedVertexHandle>,
+    vertex_zips: HashMap<FixedVertexHandle, Vec<ZipCode>>,
+}
+
+impl ZipTriangulation {
+    pub fn new(dataset: &Dataset) -> Self {
+        let mut zips: Vec<_> = dataset
+            .inner
+            .zip_index
+            .iter()
+            .map(|(zip, entry)| (zip, entry.location))
+            .collect();
+        zips.sort_unstable_by_key(|(zip, _)| *zip);
+
+        let mut triangulation = DelaunayTriangulation::new();
+        let mut vertices = HashMap::with_capacity(zips.len());
+        let mut vertex_zips = HashMap::<_, Vec<_>>::with_capacity(zips.len());
+        for (zip, (lat, lng)) in zips {
+            if let Ok(vertex) = triangulation.insert(to_plane(lat, lng)) {
+                vertices.insert(zip, vertex);
+                vertex_zips.entry(vertex).or_default().push(zip);
+            }
+        }
+
+        Self {
+            triangulation,
+            vertices,
+            vertex_zips,
+        }
+    }
+
+    /// Zips sharing the centroid or a Delaunay edge of at most
+    /// [`MAX_NEIGHBOR_DISTANCE_KM`] with `zip`, ordered by zip
+    pub fn neighbors(&self, zip: ZipCode) -> Vec<ZipCode> {
+        let Some(&vertex) = self.vertices.get(&zip) else {
+            return Vec::new();
+        };
+
+        let mut neighbors: Vec<_> = self
+            .triangulation
+            .vertex(vertex)
+            .out_edges()
+            .filter(|edge| is_short(edge.positions()))
+            .map(|edge| edge.to().fix())
+            .chain([vertex])
+            .flat_map(|vertex| self.vertex_zips[&vertex].iter().copied())
+            .filter(|&neighbor| neighbor != zip)
+            .collect();
+        neighbors.sort_unstable();
+
+        neighbors
+    }
+
+    /// Every pair of neighbouring zips once, e.g. for a smoothness penalty
+    pub fn edges(&self) -> impl Iterator<Item = (ZipCode, ZipCode)> + '_ {
+        let shared = self
+            .vertex_zips
+            .values()
+            .flat_map(|zips| pairs(zips, zips).filter(|(a, b)| a < b));
+        let adjacent = self
+            .triangulation
+            .undirected_edges()
+            .filter(|edge| is_short(edge.positions()))
+            .flat_map(|edge| {
+                let [from, to] = edge
+                    .vertices()
+                    .map(|vertex| &self.vertex_zips[&vertex.fix()]);
+                pairs(from, to)
+            });
+
+        shared.chain(adjacent)
+    }
+
+    /// The Voronoi cell around the zip's centroid as a closed ring of
+    /// `(lat, lon)` vertices. Cells on the convex hull are unbounded and
+    /// return `None`.
+    pub fn voronoi_cell(&self, zip: ZipCode) -> Option<Vec<(f64, f64)>> {
+        let vertex = self.triangulation.vertex(*self.vertices.get(&zip)?);
+
+        // Moves the whole ring by the same turn, so cells reaching across the
+        // antimeridian stay connected
+        let (_, lon) = from_plane(vertex.position());
+        let shift = geo::normalize_longitude(lon) - lon;
+
+        let mut ring = vertex
+            .as_voronoi_face()
+            .adjacent_edges()
+            .map(|edge| {
+                let (lat, lon) = from_plane(edge.from().position()?);
+                Some((lat, lon + shift))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        ring.push(*ring.first()?);
+
+        Some(ring)
+    }
+}
+
+fn to_plane(lat: f64, lon: f64) -> Point2<f64> {
+    let lon = if lon > 0.0 { lon - 360.0 } else { lon };
+    let (x, y) = projection::web_mercator(lat, lon);
+    Point2::new(x, y)
+}
+
+/// Longitudes west of -180° are left as they are
+fn from_plane(point: Point2<f64>) -> (f64, f64) {
+    projection::web_mercator_inverse(point.x, point.y)
+}
+
+fn is_short([from, to]: [Point2<f64>; 2]) -> bool {
+    geo::haversine_distance(from_plane(from), from_plane(to)) <= MAX_NEIGHBOR_DISTANCE_KM
+}
+
+fn pairs<'a>(
+    from: &'a [ZipCode],
+    to: &'a [ZipCode],
+) -> impl Iterator<Item = (ZipCode, ZipCode)> + 'a {
+    from.iter()
+        .flat_map(move |&a| to.iter().map(move |&b| (a, b)))
+}
+
+impl Dataset {
+    /// Built on first use and kept for the lifetime of the dataset
+    pub fn triangulation(&self) -> &ZipTriangulation {
+        self.inner
+            .triangulation
+            .get_or_init(|| ZipTriangulation::new(self))
+    }
+
+    pub fn neighbors(&self, zip: ZipCode) -> Vec<ZipCode> {
+        self.triangulation().neighbors(zip)
+    }
+
+    pub fn voronoi_cell(&self, zip: ZipCode) -> Option<Vec<(f64, f64)>> {
+        self.triangulation().voronoi_cell(zip)
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{geo, Dataset, ZipCode};
+use serde::{Serialize, Serializer};
+use std::io::Write;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ZipChange {
+    Added,
+    Removed,
+    Relocated,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct ZipDiff {
+    #[serde(serialize_with = "padded_zip")]
+    pub zip: ZipCode,
+    pub change: ZipChange,
+    pub old_lat: Option<f64>,
+    pub old_lng: Option<f64>,
+    pub new_lat: Option<f64>,
+    pub new_lng: Option<f64>,
+    pub distance_km: Option<f64>,
+}
+
+/// Zips added, removed or moved further than `threshold_km` between two
+/// dataset versions, ordered by zip.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DatasetDiff {
+    pub threshold_km: f64,
+    pub changes: Vec<ZipDiff>,
+}
+
+impl Dataset {
+    pub fn diff(&self, newer: &Dataset, threshold_km: f64) -> DatasetDiff {
+        let mut changes = Vec::new();
+
+        for (zip, old) in self.inner.zip_index.iter() {
+            let (old_lat, old_lng) = old.location;
+            let Some((new_lat, new_lng)) = newer.zip_location(zip) else {
+                changes.push(ZipDiff {
+                    zip,
+                    change: ZipChange::Removed,
+                    old_lat: Some(old_lat),
+                    old_lng: Some(old_lng),
+                    new_lat: None,
+                    new_lng: None,
+                    distance_km: None,
+                });
+                continue;
+            };
+
+            let distance_km = geo::haversine_distance(old.location, (new_lat, new_lng));
+            if distance_km > threshold_km {
+                changes.push(ZipDiff {
+                    zip,
+                    change: ZipChange::Relocated,
+                    old_lat: Some(old_lat),
+                    old_lng: Some(old_lng),
+                    new_lat: Some(new_lat),
+                    new_lng: Some(new_lng),
+                    distance_km: Some(distance_km),
+                });
+            }
+        }
+
+        for (zip, new) in newer.inner.zip_index.iter() {
+            if self.inner.zip_index.get(zip).is_none() {
+                changes.push(ZipDiff {
+                    zip,
+                    change: ZipChange::Added,
+                    old_lat: None,
+                    old_lng: None,
+                    new_lat: Some(new.location.0),
+                    new_lng: Some(new.location.1),
+                    distance_km: None,
+                });
+            }
+        }
+
+        changes.sort_by_key(|change| change.zip);
+
+        DatasetDiff {
+            threshold_km,
+            changes,
+        }
+    }
+}
+
+impl DatasetDiff {
+    pub fn count(&self, change: ZipChange) -> usize {
+        self.changes
+            .iter()
+            .filter(|diff| diff.change == change)
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn write_csv(&self, mut writer: impl Write) -> std::io::Result<()> {
+        let field = |value: Option<f64>| value.map(|value| value.to_string()).unwrap_or_default();
+
+        writeln!(
+            writer,
+            "zip,change,old_lat,old_lng,new_lat,new_lng,distance_km"
+        )?;
+        for diff in &self.changes {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{}",
+                diff.zip,
+                diff.change,
+                field(diff.old_lat),
+                field(diff.old_lng),
+                field(diff.new_lat),
+                field(diff.new_lng),
+                field(diff.distance_km)
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn write_json(&self, writer: impl Write) -> std::io::Result<()> {
+        Ok(serde_json::to_writer_pretty(writer, self)?)
+    }
+}
+
+impl std::fmt::Display for ZipChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ZipChange::Added => "added",
+            ZipChange::Removed => "removed",
+            ZipChange::Relocated => "relocated",
+        })
+    }
+}
+
+fn padded_zip<S: Serializer>(zip: &ZipCode, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(zip)
+}
@@ -0,0 +1,148 @@
+use polars::prelude::{DataType, PolarsError};
+use std::fmt::Display;
+
+#[derive(Debug)]
+pub enum DatasetError {
+    UnknownSource(String),
+    UnknownFormat(String),
+    UnknownSourceFormat(String),
+    UnknownProjection(String),
+    UnknownValidationPolicy(String),
+    Download(data_downloader::Error),
+    Io(std::io::Error),
+    Archive(zip::result::ZipError),
+    Checksum {
+        expected: String,
+        actual: String,
+    },
+    CsvParse(PolarsError),
+    MissingColumn(&'static str),
+    TypeMismatch {
+        column: &'static str,
+        expected: DataType,
+        actual: DataType,
+    },
+    Query(PolarsError),
+    InvalidSplit(String),
+    Validation(crate::ValidationReport),
+    GeoJson(Box<geojson::Error>),
+    Shapefile(shapefile::Error),
+}
+
+impl Display for DatasetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DatasetError::UnknownSource(name) => write!(f, "Unknown dataset source \"{name}\""),
+            DatasetError::UnknownFormat(name) => {
+                write!(
+                    f,
+                    "Unknown export format \"{name}\", expected geojson, kml or csv"
+                )
+            }
+            DatasetError::UnknownSourceFormat(name) => write!(
+                f,
+                "Unknown dataset format \"{name}\", expected simplemaps, census or geonames"
+            ),
+            DatasetError::UnknownProjection(name) => write!(
+                f,
+                "Unknown projection \"{name}\", expected latlon, web-mercator, albers-conus or ecef"
+            ),
+            DatasetError::UnknownValidationPolicy(name) => write!(
+                f,
+                "Unknown validation policy \"{name}\", expected drop, keep or error"
+            ),
+            DatasetError::Download(error) => write!(f, "Downloading dataset failed: {error}"),
+            DatasetError::Io(error) => write!(f, "Reading dataset failed: {error}"),
+            DatasetError::Archive(error) => write!(f, "Extracting dataset failed: {error}"),
+            DatasetError::Checksum { expected, actual } => write!(
+                f,
+                "Dataset checksum mismatch: expected {expected}, got {actual}"
+            ),
+            DatasetError::CsvParse(error) => write!(f, "Parsing dataset CSV failed: {error}"),
+            DatasetError::MissingColumn(column) => {
+                write!(f, "Dataset is missing column \"{column}\"")
+            }
+            DatasetError::TypeMismatch {
+                column,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Dataset column \"{column}\" has type {actual}, expected {expected}"
+            ),
+            DatasetError::Query(error) => write!(f, "Querying dataset failed: {error}"),
+            DatasetError::InvalidSplit(reason) => write!(f, "Invalid dataset split: {reason}"),
+            DatasetError::Validation(report) => write!(f, "Dataset validation failed: {report}"),
+            DatasetError::GeoJson(error) => write!(f, "Reading zip boundaries failed: {error}"),
+            DatasetError::Shapefile(error) => write!(f, "Reading zip boundaries failed: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for DatasetError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DatasetError::Download(error) => Some(error),
+            DatasetError::Io(error) => Some(error),
+            DatasetError::Archive(error) => Some(error),
+            DatasetError::CsvParse(error) | DatasetError::Query(error) => Some(error),
+            DatasetError::GeoJson(error) => Some(error),
+            DatasetError::Shapefile(error) => Some(error),
+            DatasetError::UnknownSource(_)
+            | DatasetError::UnknownFormat(_)
+            | DatasetError::UnknownSourceFormat(_)
+            | DatasetError::UnknownProjection(_)
+            | DatasetError::UnknownValidationPolicy(_)
+            | DatasetError::Checksum { .. }
+            | DatasetError::MissingColumn(_)
+            | DatasetError::TypeMismatch { .. }
+            | DatasetError::InvalidSplit(_)
+            | DatasetError::Validation(_) => None,
+        }
+    }
+}
+
+impl From<data_downloader::Error> for DatasetError {
+    fn from(error: data_downloader::Error) -> Self {
+        match error {
+            data_downloader::Error::DownloadHashMismatch(mismatch)
+            | data_downloader::Error::OnDiskHashMismatch(mismatch)
+            | data_downloader::Error::ZipContentsHashMismatch(mismatch)
+            | data_downloader::Error::ManualHashMismatch(mismatch) => DatasetError::Checksum {
+                expected: mismatch.expected,
+                actual: mismatch.was,
+            },
+            error => DatasetError::Download(error),
+        }
+    }
+}
+
+impl From<std::io::Error> for DatasetError {
+    fn from(error: std::io::Error) -> Self {
+        DatasetError::Io(error)
+    }
+}
+
+impl From<zip::result::ZipError> for DatasetError {
+    fn from(error: zip::result::ZipError) -> Self {
+        DatasetError::Archive(error)
+    }
+}
+
+impl From<PolarsError> for DatasetError {
+    fn from(error: PolarsError) -> Self {
+        DatasetError::Query(error)
+    }
+}
+
+impl From<geojson::Error> for DatasetError {
+    fn from(error: geojson::Error) -> Self {
+        DatasetError::GeoJson(Box::new(error))
+    }
+}
+
+impl From<shapefile::Error> for DatasetError {
+    fn from(error: shapefile::Error) -> Self {
+        DatasetError::Shapefile(error)
+    }
+}
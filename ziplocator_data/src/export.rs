@@ -0,0 +1,217 @@
+use crate::{geo, Dataset, DatasetError, ZipCode, ZipRecord};
+use serde_json::{json, Map, Value};
+use std::{collections::HashMap, fmt::Display, io::Write, str::FromStr};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    GeoJson,
+    Kml,
+    Csv,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Prediction {
+    pub zip: ZipCode,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// Writes the dataset for GIS tools. Rows with a prediction get
+/// `predicted_lat`, `predicted_lng` and `error_km` columns.
+pub struct Exporter<'a> {
+    dataset: &'a Dataset,
+    predictions: HashMap<ZipCode, (f64, f64)>,
+}
+
+impl Dataset {
+    pub fn exporter(&self) -> Exporter<'_> {
+        Exporter {
+            dataset: self,
+            predictions: HashMap::new(),
+        }
+    }
+}
+
+impl Exporter<'_> {
+    pub fn predictions(mut self, predictions: impl IntoIterator<Item = Prediction>) -> Self {
+        self.predictions.extend(
+            predictions
+                .into_iter()
+                .map(|prediction| (prediction.zip, (prediction.latitude, prediction.longitude))),
+        );
+        self
+    }
+
+    pub fn write(&self, format: ExportFormat, writer: impl Write) -> std::io::Result<()> {
+        match format {
+            ExportFormat::GeoJson => self.write_geojson(writer),
+            ExportFormat::Kml => self.write_kml(writer),
+            ExportFormat::Csv => self.write_csv(writer),
+        }
+    }
+
+    pub fn write_geojson(&self, writer: impl Write) -> std::io::Result<()> {
+        let features: Vec<_> = self
+            .dataset
+            .records()
+            .map(|record| {
+                let properties: Map<_, _> = self
+                    .fields(&record)
+                    .into_iter()
+                    .map(|(name, value)| (name.to_string(), value))
+                    .collect();
+
+                json!({
+                    "type": "Feature",
+                    "geometry": {
+                        "type": "Point",
+                        "coordinates": [record.longitude, record.latitude],
+                    },
+                    "properties": properties,
+                })
+            })
+            .collect();
+
+        let collection = json!({
+            "type": "FeatureCollection",
+            "features": features,
+        });
+
+        Ok(serde_json::to_writer(writer, &collection)?)
+    }
+
+    pub fn write_kml(&self, mut writer: impl Write) -> std::io::Result<()> {
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(writer, r#"<kml xmlns="http://www.opengis.net/kml/2.2">"#)?;
+        writeln!(writer, "<Document>")?;
+
+        for record in self.dataset.records() {
+            writeln!(writer, "<Placemark>")?;
+            writeln!(writer, "<name>{}</name>", record.zip)?;
+            writeln!(
+                writer,
+                "<description>{}, {}</description>",
+                xml_escape(&record.city),
+                xml_escape(&record.state_id)
+            )?;
+
+            writeln!(writer, "<ExtendedData>")?;
+            for (name, value) in self.fields(&record) {
+                writeln!(
+                    writer,
+                    r#"<Data name="{name}"><value>{}</value></Data>"#,
+                    xml_escape(&plain(&value))
+                )?;
+            }
+            writeln!(writer, "</ExtendedData>")?;
+
+            writeln!(
+                writer,
+                "<Point><coordinates>{},{}</coordinates></Point>",
+                record.longitude, record.latitude
+            )?;
+            writeln!(writer, "</Placemark>")?;
+        }
+
+        writeln!(writer, "</Document>")?;
+        writeln!(writer, "</kml>")
+    }
+
+    pub fn write_csv(&self, mut writer: impl Write) -> std::io::Result<()> {
+        let mut header_written = false;
+
+        for record in self.dataset.records() {
+            let fields = self.fields(&record);
+            if !header_written {
+                let names: Vec<_> = fields.iter().map(|(name, _)| *name).collect();
+                writeln!(writer, "{}", names.join(","))?;
+                header_written = true;
+            }
+
+            let values: Vec<_> = fields
+                .iter()
+                .map(|(_, value)| csv_escape(&plain(value)))
+                .collect();
+            writeln!(writer, "{}", values.join(","))?;
+        }
+
+        Ok(())
+    }
+
+    fn fields(&self, record: &ZipRecord) -> Vec<(&'static str, Value)> {
+        let mut fields = vec![
+            ("zip", json!(record.zip.to_string())),
+            ("lat", json!(record.latitude)),
+            ("lng", json!(record.longitude)),
+            ("city", json!(record.city)),
+            ("state_id", json!(record.state_id)),
+            ("state_name", json!(record.state_name)),
+            ("county_name", json!(record.county_name)),
+            ("population", json!(record.population)),
+            ("density", json!(record.density)),
+            ("timezone", json!(record.timezone)),
+        ];
+
+        if !self.predictions.is_empty() {
+            let prediction = self.predictions.get(&record.zip).copied();
+            let error_km = prediction.map(|predicted| {
+                geo::haversine_distance((record.latitude, record.longitude), predicted)
+            });
+            fields.extend([
+                ("predicted_lat", json!(prediction.map(|(lat, _)| lat))),
+                ("predicted_lng", json!(prediction.map(|(_, lng)| lng))),
+                ("error_km", json!(error_km)),
+            ]);
+        }
+
+        fields
+    }
+}
+
+/// Field values without JSON quoting, nulls become empty
+fn plain(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(string) => string.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ExportFormat::GeoJson => "geojson",
+            ExportFormat::Kml => "kml",
+            ExportFormat::Csv => "csv",
+        })
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = DatasetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "geojson" | "json" => Ok(ExportFormat::GeoJson),
+            "kml" => Ok(ExportFormat::Kml),
+            "csv" => Ok(ExportFormat::Csv),
+            _ => Err(DatasetError::UnknownFormat(s.to_string())),
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{geo, Dataset, DatasetError};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Territories and freely associated states with US zip codes
+pub const TERRITORIES: &[&str] = &["AS", "FM", "GU", "MH", "MP", "PR", "PW", "UM", "VI"];
+
+/// The contiguous 48 states plus DC
+pub const CONTIGUOUS_STATES: &[&str] = &[
+    "AL", "AR", "AZ", "CA", "CO", "CT", "DC", "DE", "FL", "GA", "IA", "ID", "IL", "IN", "KS", "KY",
+    "LA", "MA", "MD", "ME", "MI", "MN", "MO", "MS", "MT", "NC", "ND", "NE", "NH", "NJ", "NM", "NV",
+    "NY", "OH", "OK", "OR", "PA", "RI", "SC", "SD", "TN", "TX", "UT", "VA", "VT", "WA", "WI", "WV",
+    "WY",
+];
+
+/// Builder for the rows kept by [`Dataset::filter`]. All set conditions have
+/// to hold for a row to be kept.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DatasetFilter {
+    states: Option<Vec<String>>,
+    excluded_states: Vec<String>,
+    bounds: Option<geo::BoundingBox>,
+    polygon: Option<Vec<(f64, f64)>>,
+}
+
+impl DatasetFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn contiguous_48() -> Self {
+        Self::new().states(CONTIGUOUS_STATES.iter().copied())
+    }
+
+    /// Only keeps zips in the given states, by two letter state id
+    pub fn states<S: AsRef<str>>(mut self, states: impl IntoIterator<Item = S>) -> Self {
+        self.states = Some(
+            states
+                .into_iter()
+                .map(|state| state.as_ref().trim().to_ascii_uppercase())
+                .collect(),
+        );
+        self
+    }
+
+    pub fn exclude_states<S: AsRef<str>>(mut self, states: impl IntoIterator<Item = S>) -> Self {
+        self.excluded_states.extend(
+            states
+                .into_iter()
+                .map(|state| state.as_ref().trim().to_ascii_uppercase()),
+        );
+        self
+    }
+
+    pub fn territories(self, include: bool) -> Self {
+        if include {
+            let mut filter = self;
+            filter
+                .excluded_states
+                .retain(|state| !TERRITORIES.contains(&state.as_str()));
+            if let Some(states) = &mut filter.states {
+                states.extend(TERRITORIES.iter().map(|territory| territory.to_string()));
+            }
+            filter
+        } else {
+            self.exclude_states(TERRITORIES.iter().copied())
+        }
+    }
+
+    pub fn bounding_box(mut self, bounds: geo::BoundingBox) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Only keeps zips inside a ring of `(lat, lon)` vertices
+    pub fn polygon(mut self, polygon: impl Into<Vec<(f64, f64)>>) -> Self {
+        self.polygon = Some(polygon.into());
+        self
+    }
+
+    fn keeps(&self, state: &str, lat: f64, lon: f64) -> bool {
+        self.states
+            .as_ref()
+            .is_none_or(|states| states.iter().any(|kept| kept == state))
+            && !self
+                .excluded_states
+                .iter()
+                .any(|excluded| excluded == state)
+            && self.bounds.is_none_or(|bounds| bounds.contains(lat, lon))
+            && self
+                .polygon
+                .as_ref()
+                .is_none_or(|polygon| geo::polygon_contains(polygon, lat, lon))
+    }
+}
+
+impl Dataset {
+    pub fn filter(&self, filter: &DatasetFilter) -> Result<Dataset, DatasetError> {
+        let states = self.dataframe().column("state_id")?.str()?;
+        let lats = self.dataframe().column("lat")?.f64()?;
+        let lngs = self.dataframe().column("lng")?.f64()?;
+
+        let rows = states
+            .into_iter()
+            .zip(lats)
+            .zip(lngs)
+            .enumerate()
+            .filter_map(|(row, ((state, lat), lng))| {
+                let keep = filter.keeps(state.unwrap_or_default(), lat?, lng?);
+                keep.then_some(row as IdxSize)
+            })
+            .collect();
+
+        self.take_rows(rows)
+    }
+}
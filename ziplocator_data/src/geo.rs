@@ -0,0 +1,220 @@
+use serde::{Deserialize, Serialize};
+
+pub const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// Great-circle distance in kilometres between two `(lat, lon)` points given
+/// in degrees.
+pub fn haversine_distance(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (lat1, lon1) = (from.0.to_radians(), from.1.to_radians());
+    let (lat2, lon2) = (to.0.to_radians(), to.1.to_radians());
+
+    let a = ((lat2 - lat1) / 2.0).sin().powi(2)
+        + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+}
+
+/// WGS 84 ellipsoid, used by [`vincenty_distance`]
+pub const WGS84_SEMI_MAJOR_AXIS_KM: f64 = 6378.137;
+pub const WGS84_FLATTENING: f64 = 1.0 / 298.257223563;
+
+/// Ellipsoidal distance in kilometres, accurate to a few millimetres. Returns
+/// `None` for nearly antipodal points, where the iteration does not converge.
+pub fn vincenty_distance(from: (f64, f64), to: (f64, f64)) -> Option<f64> {
+    let (a, f) = (WGS84_SEMI_MAJOR_AXIS_KM, WGS84_FLATTENING);
+    let b = a * (1.0 - f);
+
+    let u1 = ((1.0 - f) * from.0.to_radians().tan()).atan();
+    let u2 = ((1.0 - f) * to.0.to_radians().tan()).atan();
+    let l = (to.1 - from.1).to_radians();
+    let (sin_u1, cos_u1) = u1.sin_cos();
+    let (sin_u2, cos_u2) = u2.sin_cos();
+
+    let mut lambda = l;
+    for _ in 0..200 {
+        let (sin_lambda, cos_lambda) = lambda.sin_cos();
+        let sin_sigma = ((cos_u2 * sin_lambda).powi(2)
+            + (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2))
+        .sqrt();
+        if sin_sigma == 0.0 {
+            return Some(0.0);
+        }
+
+        let cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+        let sigma = sin_sigma.atan2(cos_sigma);
+        let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+        let cos2_alpha = 1.0 - sin_alpha.powi(2);
+        // Both points on the equator
+        let cos_2sigma_m = if cos2_alpha == 0.0 {
+            0.0
+        } else {
+            cos_sigma - 2.0 * sin_u1 * sin_u2 / cos2_alpha
+        };
+
+        let c = f / 16.0 * cos2_alpha * (4.0 + f * (4.0 - 3.0 * cos2_alpha));
+        let previous = lambda;
+        lambda = l
+            + (1.0 - c)
+                * f
+                * sin_alpha
+                * (sigma
+                    + c * sin_sigma
+                        * (cos_2sigma_m + c * cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))));
+
+        if (lambda - previous).abs() < 1e-12 {
+            let u2 = cos2_alpha * (a * a - b * b) / (b * b);
+            let big_a = 1.0 + u2 / 16384.0 * (4096.0 + u2 * (-768.0 + u2 * (320.0 - 175.0 * u2)));
+            let big_b = u2 / 1024.0 * (256.0 + u2 * (-128.0 + u2 * (74.0 - 47.0 * u2)));
+            let delta_sigma = big_b
+                * sin_sigma
+                * (cos_2sigma_m
+                    + big_b / 4.0
+                        * (cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))
+                            - big_b / 6.0
+                                * cos_2sigma_m
+                                * (-3.0 + 4.0 * sin_sigma.powi(2))
+                                * (-3.0 + 4.0 * cos_2sigma_m.powi(2))));
+
+            return Some(b * big_a * (sigma - delta_sigma));
+        }
+    }
+
+    None
+}
+
+/// Initial great-circle bearing in degrees clockwise from north, `0..360`.
+pub fn initial_bearing(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (lat1, lat2) = (from.0.to_radians(), to.0.to_radians());
+    let delta_lon = (to.1 - from.1).to_radians();
+
+    let y = delta_lon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * delta_lon.cos();
+
+    y.atan2(x).to_degrees().rem_euclid(360.0)
+}
+
+/// Halfway point along the great circle between two points.
+pub fn midpoint(from: (f64, f64), to: (f64, f64)) -> (f64, f64) {
+    centroid([from, to]).unwrap_or(from)
+}
+
+/// Point reached by travelling `distance_km` along the great circle starting
+/// at `bearing` degrees clockwise from north.
+pub fn destination_point(from: (f64, f64), bearing: f64, distance_km: f64) -> (f64, f64) {
+    let (lat, lon) = (from.0.to_radians(), from.1.to_radians());
+    let bearing = bearing.to_radians();
+    let angle = distance_km / EARTH_RADIUS_KM;
+
+    let lat2 = (lat.sin() * angle.cos() + lat.cos() * angle.sin() * bearing.cos()).asin();
+    let lon2 =
+        lon + (bearing.sin() * angle.sin() * lat.cos()).atan2(angle.cos() - lat.sin() * lat2.sin());
+
+    (lat2.to_degrees(), normalize_longitude(lon2.to_degrees()))
+}
+
+/// Wraps a longitude into `-180..180`.
+pub fn normalize_longitude(lon: f64) -> f64 {
+    (lon + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Mean position on the sphere, so points on both sides of the antimeridian
+/// average to a point between them.
+pub fn centroid(points: impl IntoIterator<Item = (f64, f64)>) -> Option<(f64, f64)> {
+    let (mut sum, mut count) = ([0.0; 3], 0);
+    for (lat, lon) in points {
+        let vector = unit_vector(lat, lon);
+        sum.iter_mut().zip(vector).for_each(|(sum, v)| *sum += v);
+        count += 1;
+    }
+
+    let [x, y, z] = sum;
+    (count > 0).then(|| (z.atan2(x.hypot(y)).to_degrees(), y.atan2(x).to_degrees()))
+}
+
+pub(crate) fn unit_vector(lat: f64, lon: f64) -> [f64; 3] {
+    let (lat, lon) = (lat.to_radians(), lon.to_radians());
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BoundingBox {
+    pub min_lat: f64,
+    pub max_lat: f64,
+    pub min_lon: f64,
+    pub max_lon: f64,
+}
+
+impl BoundingBox {
+    pub const fn new(min_lat: f64, max_lat: f64, min_lon: f64, max_lon: f64) -> Self {
+        Self {
+            min_lat,
+            max_lat,
+            min_lon,
+            max_lon,
+        }
+    }
+
+    pub fn contains(&self, lat: f64, lon: f64) -> bool {
+        (self.min_lat..=self.max_lat).contains(&lat) && (self.min_lon..=self.max_lon).contains(&lon)
+    }
+}
+
+/// Generous boxes around every region with US zip codes, including the
+/// freely associated states in [`crate::TERRITORIES`]. The Aleutians cross
+/// the antimeridian, so Alaska needs two.
+pub const US_BOUNDING_BOXES: &[(&str, BoundingBox)] = &[
+    ("Contiguous US", BoundingBox::new(24.0, 50.0, -125.5, -66.5)),
+    ("Alaska", BoundingBox::new(51.0, 72.0, -180.0, -129.5)),
+    (
+        "Alaska (Aleutians)",
+        BoundingBox::new(51.0, 53.5, 172.0, 180.0),
+    ),
+    ("Hawaii", BoundingBox::new(18.5, 22.5, -161.0, -154.5)),
+    (
+        "Puerto Rico and U.S. Virgin Islands",
+        BoundingBox::new(17.5, 18.8, -68.0, -64.5),
+    ),
+    (
+        "Guam and Northern Mariana Islands",
+        BoundingBox::new(13.0, 21.0, 144.5, 146.5),
+    ),
+    (
+        "American Samoa",
+        BoundingBox::new(-14.8, -10.8, -171.5, -168.0),
+    ),
+    ("Wake Island", BoundingBox::new(19.1, 19.5, 166.4, 166.8)),
+    ("Midway Atoll", BoundingBox::new(28.1, 28.5, -177.6, -177.2)),
+    ("Palau", BoundingBox::new(2.8, 8.2, 131.0, 134.8)),
+    ("Micronesia", BoundingBox::new(0.8, 10.2, 137.5, 163.5)),
+    (
+        "Marshall Islands",
+        BoundingBox::new(4.5, 14.8, 160.5, 172.5),
+    ),
+];
+
+pub fn in_us(lat: f64, lon: f64) -> bool {
+    US_BOUNDING_BOXES
+        .iter()
+        .any(|(_, bounds)| bounds.contains(lat, lon))
+}
+
+/// Even-odd point-in-polygon test on a ring of `(lat, lon)` vertices. The
+/// ring may be open or closed.
+pub fn polygon_contains(polygon: &[(f64, f64)], lat: f64, lon: f64) -> bool {
+    let mut inside = false;
+    let mut previous = match polygon.last() {
+        Some(&vertex) => vertex,
+        None => return false,
+    };
+
+    for &vertex in polygon {
+        let ((lat1, lon1), (lat2, lon2)) = (previous, vertex);
+        if (lat1 > lat) != (lat2 > lat) && lon < lon1 + (lat - lat1) / (lat2 - lat1) * (lon2 - lon1)
+        {
+            inside = !inside;
+        }
+        previous = vertex;
+    }
+
+    inside
+}
@@ -0,0 +1,104 @@
+use crate::{
+    geo::{self, unit_vector},
+    DatasetError, ZipCode, ZipDistance,
+};
+use polars::prelude::*;
+use rstar::{primitives::GeomWithData, RTree};
+use std::collections::HashMap;
+
+type IndexedZip = GeomWithData<[f64; 3], (ZipCode, f64, f64)>;
+
+/// Zip centroids as unit vectors, so straight-line (chord) distances order
+/// points the same way great-circle distances do, including across the poles
+/// and the antimeridian.
+#[derive(Clone, Debug)]
+pub(crate) struct SpatialIndex(RTree<IndexedZip>);
+
+impl SpatialIndex {
+    pub fn new(dataframe: &DataFrame) -> Result<Self, DatasetError> {
+        let zips = dataframe.column("zip")?.u32()?;
+        let lats = dataframe.column("lat")?.f64()?;
+        let lngs = dataframe.column("lng")?.f64()?;
+
+        let points = zips
+            .into_iter()
+            .zip(lats)
+            .zip(lngs)
+            .filter_map(|((zip, lat), lng)| {
+                let (zip, lat, lng) = (ZipCode::new(zip?).ok()?, lat?, lng?);
+                Some(IndexedZip::new(unit_vector(lat, lng), (zip, lat, lng)))
+            })
+            .collect();
+
+        Ok(Self(RTree::bulk_load(points)))
+    }
+
+    pub fn nearest(&self, lat: f64, lon: f64, k: usize) -> Vec<ZipDistance> {
+        self.0
+            .nearest_neighbor_iter(&unit_vector(lat, lon))
+            .take(k)
+            .map(|zip| distance(zip, lat, lon))
+            .collect()
+    }
+
+    pub fn within_radius(&self, lat: f64, lon: f64, radius_km: f64) -> Vec<ZipDistance> {
+        let angle = (radius_km / geo::EARTH_RADIUS_KM).min(std::f64::consts::PI);
+        let chord = 2.0 * (angle / 2.0).sin();
+
+        let mut zips: Vec<_> = self
+            .0
+            .locate_within_distance(unit_vector(lat, lon), chord * chord)
+            .map(|zip| distance(zip, lat, lon))
+            .filter(|zip| zip.distance_km <= radius_km)
+            .collect();
+        zips.sort_by(|a, b| a.distance_km.total_cmp(&b.distance_km));
+
+        zips
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ZipEntry {
+    pub row: usize,
+    pub location: (f64, f64),
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct ZipIndex(HashMap<ZipCode, ZipEntry>);
+
+impl ZipIndex {
+    pub fn new(dataframe: &DataFrame) -> Result<Self, DatasetError> {
+        let zips = dataframe.column("zip")?.u32()?;
+        let lats = dataframe.column("lat")?.f64()?;
+        let lngs = dataframe.column("lng")?.f64()?;
+
+        let mut entries = HashMap::with_capacity(dataframe.height());
+        for (row, ((zip, lat), lng)) in zips.into_iter().zip(lats).zip(lngs).enumerate() {
+            let zip = zip.and_then(|zip| ZipCode::new(zip).ok());
+            if let (Some(zip), Some(lat), Some(lng)) = (zip, lat, lng) {
+                entries.entry(zip).or_insert(ZipEntry {
+                    row,
+                    location: (lat, lng),
+                });
+            }
+        }
+
+        Ok(Self(entries))
+    }
+
+    pub fn get(&self, zip: ZipCode) -> Option<&ZipEntry> {
+        self.0.get(&zip)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (ZipCode, &ZipEntry)> {
+        self.0.iter().map(|(&zip, entry)| (zip, entry))
+    }
+}
+
+fn distance(zip: &IndexedZip, lat: f64, lon: f64) -> ZipDistance {
+    let (code, zip_lat, zip_lng) = zip.data;
+    ZipDistance {
+        zip: code,
+        distance_km: geo::haversine_distance((lat, lon), (zip_lat, zip_lng)),
+    }
+}
@@ -1,100 +1,161 @@
-use data_downloader::{DownloadRequest, InZipDownloadRequest};
-use hex_literal::hex;
+mod boundary;
+mod cache;
+mod delaunay;
+mod diff;
+mod error;
+mod export;
+mod filter;
+pub mod geo;
+mod index;
+mod load;
+mod prefix;
+pub mod projection;
+mod record;
+mod source;
+mod split;
+mod summary;
+mod synthetic;
+mod validate;
+mod zip_code;
+
+pub use boundary::*;
+pub use delaunay::*;
+pub use diff::*;
+pub use error::*;
+pub use export::*;
+pub use filter::*;
+pub use load::*;
+pub use prefix::*;
+pub use record::*;
+pub use source::*;
+pub use split::*;
+pub use summary::*;
+pub use validate::*;
+pub use zip_code::*;
+
 use polars::prelude::*;
-use std::io::Cursor;
+use std::{
+    io::Read,
+    path::Path,
+    sync::{Arc, OnceLock},
+};
+
+/// A loaded dataset and its indexes. Clones share the same data, so one
+/// dataset can be handed to several threads.
+#[derive(Clone)]
+pub struct Dataset {
+    inner: Arc<DatasetInner>,
+    boundaries: Option<Arc<ZipBoundaries>>,
+}
+
+struct DatasetInner {
+    dataframe: DataFrame,
+    spatial_index: index::SpatialIndex,
+    zip_index: index::ZipIndex,
+    triangulation: OnceLock<ZipTriangulation>,
+}
 
-const DATASET_URL: &str =
-    "https://simplemaps.com/static/data/us-zips/1.90/basic/simplemaps_uszips_basicv1.90.zip";
+// Shared between the trainer, evaluator and the UI's workers
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Dataset>();
+};
 
-pub struct Dataset(DataFrame);
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ZipDistance {
+    pub zip: ZipCode,
+    pub distance_km: f64,
+}
 
 impl Dataset {
-    pub fn load() -> Self {
-        let dataset = data_downloader::get(&InZipDownloadRequest {
-            parent: &DownloadRequest {
-                url: DATASET_URL,
-                sha256_hash: &hex!(
-                    "911765CB2433F7BDFF22D2817CA1B96BDE8F4B6F5C10FB9AEA1F3310DC04F1F8"
-                ),
-            },
-            path: "uszips.csv",
-            sha256_hash: &hex!("0B8F9D378D8868F42324788A457A17434E38BB364060055D5C338A2FFE512285"),
+    pub fn load() -> Result<Self, DatasetError> {
+        DatasetLoader::new().load()
+    }
+
+    /// Reads a local copy of the default source, see
+    /// [`DatasetSource::load_path`]. Rows failing validation are dropped.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, DatasetError> {
+        DatasetSource::default().load_path(path)
+    }
+
+    pub fn from_reader(reader: impl Read) -> Result<Self, DatasetError> {
+        DatasetSource::default().load_reader(reader)
+    }
+
+    pub(crate) fn from_dataframe(dataframe: DataFrame) -> Result<Self, DatasetError> {
+        let spatial_index = index::SpatialIndex::new(&dataframe)?;
+        let zip_index = index::ZipIndex::new(&dataframe)?;
+
+        Ok(Dataset {
+            inner: Arc::new(DatasetInner {
+                dataframe,
+                spatial_index,
+                zip_index,
+                triangulation: OnceLock::new(),
+            }),
+            boundaries: None,
         })
-        .expect("Downloading dataset");
-
-        let dataset = Cursor::new(dataset);
-
-        let dataframe = CsvReadOptions::default()
-            .with_has_header(true)
-            .into_reader_with_file_handle(dataset)
-            .finish()
-            .expect("Create dataframe")
-            .select(["zip", "lat", "lng"])
-            .expect("Selecting columns");
-
-        Dataset(dataframe)
-    }
-
-    pub fn dataframe(&self) -> DataFrame {
-        self.0.clone()
-    }
-
-    pub fn zip_location(&self, zip: u32) -> Option<(f64, f64)> {
-        let matching_zips = self
-            .0
-            .clone()
-            .lazy()
-            .filter(col("zip").eq(zip))
-            .collect()
-            .expect("Collecting matching zips");
-
-        if matching_zips.height() > 0 {
-            let lat = matching_zips
-                .column("lat")
-                .expect("Latitude")
-                .f64()
-                .ok()?
-                .get(0)?;
-            let lng = matching_zips
-                .column("lng")
-                .expect("Longitude")
-                .f64()
-                .ok()?
-                .get(0)?;
-
-            Some((lat, lng))
-        } else {
-            None
-        }
-    }
-
-    pub fn nearest_zip(&self, lat: f64, lon: f64) -> Option<u32> {
-        let lat = lit(lat);
-        let lon = lit(lon);
-
-        let zips_descending = self
-            .0
-            .clone()
-            .lazy()
-            .with_columns([
-                (col("lat") - lat).abs().alias("lat_diff"),
-                (col("lng") - lon).abs().alias("lng_diff"),
-            ])
-            .with_columns([(col("lat_diff") + col("lng_diff")).alias("loc_diff")])
-            .sort(["loc_diff"], SortMultipleOptions::default())
-            .collect()
-            .expect("Finding nearest zip code");
-
-        if zips_descending.height() > 0 {
-            zips_descending
-                .column("zip")
-                .ok()?
-                .cast(&DataType::UInt32)
-                .ok()?
-                .try_u32()?
-                .get(0)
-        } else {
-            None
-        }
+    }
+
+    pub(crate) fn take_rows(&self, mut rows: Vec<IdxSize>) -> Result<Self, DatasetError> {
+        rows.sort_unstable();
+        let rows = IdxCa::from_vec("row".into(), rows);
+        let mut dataset = Self::from_dataframe(self.inner.dataframe.take(&rows)?)?;
+        dataset.boundaries = self.boundaries.clone();
+        Ok(dataset)
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.dataframe.height()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.dataframe.is_empty()
+    }
+
+    pub fn dataframe(&self) -> &DataFrame {
+        &self.inner.dataframe
+    }
+
+    /// A lazy query over the dataset's rows, e.g. to select columns without
+    /// copying the dataframe
+    pub fn lazy(&self) -> LazyFrame {
+        self.inner.dataframe.clone().lazy()
+    }
+
+    pub fn zip_row(&self, zip: ZipCode) -> Option<usize> {
+        self.inner.zip_index.get(zip).map(|entry| entry.row)
+    }
+
+    pub fn zip_location(&self, zip: ZipCode) -> Option<(f64, f64)> {
+        self.inner.zip_index.get(zip).map(|entry| entry.location)
+    }
+
+    pub fn zip_locations(&self, zips: &[ZipCode]) -> Vec<Option<(f64, f64)>> {
+        zips.iter().map(|&zip| self.zip_location(zip)).collect()
+    }
+
+    /// Great-circle distance in kilometres between two zips' centroids
+    pub fn distance_between(&self, zip_a: ZipCode, zip_b: ZipCode) -> Option<f64> {
+        Some(geo::haversine_distance(
+            self.zip_location(zip_a)?,
+            self.zip_location(zip_b)?,
+        ))
+    }
+
+    pub fn nearest_zip(&self, lat: f64, lon: f64) -> Option<ZipCode> {
+        self.inner
+            .spatial_index
+            .nearest(lat, lon, 1)
+            .first()
+            .map(|nearest| nearest.zip)
+    }
+
+    pub fn k_nearest(&self, lat: f64, lon: f64, k: usize) -> Vec<ZipDistance> {
+        self.inner.spatial_index.nearest(lat, lon, k)
+    }
+
+    pub fn within_radius(&self, lat: f64, lon: f64, radius_km: f64) -> Vec<ZipDistance> {
+        self.inner.spatial_index.within_radius(lat, lon, radius_km)
     }
 }
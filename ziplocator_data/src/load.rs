@@ -0,0 +1,174 @@
+use crate::{
+    cache, Dataset, DatasetError, DatasetSource, SourceFormat, ValidationPolicy, ValidationReport,
+    ZipBoundaries, BOUNDARIES_ENV, DATASET_ENV,
+};
+use data_downloader::{DownloadRequest, Downloader, InZipDownloadRequest};
+use std::path::PathBuf;
+
+pub const CACHE_DIR_ENV: &str = "ZIPLOCATOR_CACHE_DIR";
+pub const DATASET_PATH_ENV: &str = "ZIPLOCATOR_DATASET_PATH";
+pub const DATASET_FORMAT_ENV: &str = "ZIPLOCATOR_DATASET_FORMAT";
+
+/// Builder for loading the dataset through the download cache.
+///
+/// The cache directory defaults to `$ZIPLOCATOR_CACHE_DIR` and falls back to
+/// the platform cache directory used by `data_downloader`. Unless disabled,
+/// the parsed dataframe is also cached there as Parquet. The source defaults
+/// to the one named by `$ZIPLOCATOR_DATASET`, or simplemaps 1.90. Instead of
+/// downloading, a local copy can be read from `$ZIPLOCATOR_DATASET_PATH`, see
+/// [`DatasetLoader::path`]. Rows failing validation are dropped unless
+/// another policy is set. ZCTA boundaries are loaded from
+/// `$ZIPLOCATOR_BOUNDARIES` if set.
+#[derive(Clone, Debug)]
+pub struct DatasetLoader {
+    cache_dir: Option<PathBuf>,
+    parquet_cache: bool,
+    source: Result<DatasetSource, String>,
+    path: Option<PathBuf>,
+    format: Option<Result<SourceFormat, String>>,
+    validation: ValidationPolicy,
+    boundaries: Option<PathBuf>,
+}
+
+impl Default for DatasetLoader {
+    fn default() -> Self {
+        let source = match std::env::var(DATASET_ENV) {
+            Ok(name) => DatasetSource::find(&name).ok_or(name),
+            Err(_) => Ok(DatasetSource::default()),
+        };
+
+        Self {
+            cache_dir: std::env::var_os(CACHE_DIR_ENV).map(PathBuf::from),
+            parquet_cache: true,
+            source,
+            path: std::env::var_os(DATASET_PATH_ENV).map(PathBuf::from),
+            format: std::env::var(DATASET_FORMAT_ENV)
+                .ok()
+                .map(|name| name.parse().map_err(|_| name)),
+            validation: ValidationPolicy::default(),
+            boundaries: std::env::var_os(BOUNDARIES_ENV).map(PathBuf::from),
+        }
+    }
+}
+
+impl DatasetLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(cache_dir.into());
+        self
+    }
+
+    pub fn parquet_cache(mut self, enabled: bool) -> Self {
+        self.parquet_cache = enabled;
+        self
+    }
+
+    pub fn source(mut self, source: DatasetSource) -> Self {
+        self.source = Ok(source);
+        self
+    }
+
+    /// Reads a local copy of the source instead of downloading it, either
+    /// its archive or the file inside it, verified against the source's
+    /// checksums. With a [`DatasetLoader::format`], the file is parsed in
+    /// that format without verification instead.
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Format of an unverified local file set with [`DatasetLoader::path`],
+    /// e.g. a newer simplemaps release or a Census Gazetteer file
+    pub fn format(mut self, format: SourceFormat) -> Self {
+        self.format = Some(Ok(format));
+        self
+    }
+
+    /// Downloads the source even if `$ZIPLOCATOR_DATASET_PATH` is set,
+    /// unless [`DatasetLoader::path`] is called afterwards.
+    pub fn download(mut self) -> Self {
+        self.path = None;
+        self.format = None;
+        self
+    }
+
+    pub fn validation(mut self, policy: ValidationPolicy) -> Self {
+        self.validation = policy;
+        self
+    }
+
+    /// A ZCTA boundary shapefile or GeoJSON file, see [`ZipBoundaries`]
+    pub fn boundaries(mut self, path: impl Into<PathBuf>) -> Self {
+        self.boundaries = Some(path.into());
+        self
+    }
+
+    pub fn load(self) -> Result<Dataset, DatasetError> {
+        self.load_with_report().map(|(dataset, _)| dataset)
+    }
+
+    pub fn load_with_report(self) -> Result<(Dataset, ValidationReport), DatasetError> {
+        let validation = self.validation;
+        let boundaries = self
+            .boundaries
+            .as_ref()
+            .map(ZipBoundaries::from_path)
+            .transpose()?;
+
+        let (mut dataset, report) = self.load_unvalidated()?.validate(validation)?;
+        if let Some(boundaries) = boundaries {
+            dataset = dataset.with_boundaries(boundaries);
+        }
+
+        Ok((dataset, report))
+    }
+
+    fn load_unvalidated(self) -> Result<Dataset, DatasetError> {
+        let source = self.source.map_err(DatasetError::UnknownSource)?;
+
+        if let Some(path) = self.path {
+            return match self.format {
+                Some(format) => format
+                    .map_err(DatasetError::UnknownSourceFormat)?
+                    .parse_path(path),
+                None => source.parse_path(path),
+            };
+        }
+
+        let mut downloader = Downloader::builder();
+        if let Some(cache_dir) = self.cache_dir {
+            std::fs::create_dir_all(&cache_dir)?;
+            downloader = downloader.storage_dir(cache_dir);
+        }
+
+        let downloader = downloader.build()?;
+        let request = InZipDownloadRequest {
+            parent: &DownloadRequest {
+                url: source.url,
+                sha256_hash: &source.archive_sha256,
+            },
+            path: source.path,
+            sha256_hash: &source.sha256,
+        };
+
+        if !self.parquet_cache {
+            return source.parse(downloader.get(&request)?);
+        }
+
+        let parquet_path = cache::parquet_path(&downloader.get_path(&request)?);
+        if let Ok(dataframe) = cache::read_parquet(&parquet_path) {
+            if let Ok(dataset) = Dataset::from_dataframe(dataframe) {
+                return Ok(dataset);
+            }
+        }
+
+        let dataset = source.parse(downloader.get(&request)?)?;
+        // The cache only speeds up the next start, so failing to write it is not fatal
+        cache::write_parquet(&parquet_path, dataset.dataframe()).ok();
+
+        Ok(dataset)
+    }
+}
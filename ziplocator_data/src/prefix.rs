@@ -0,0 +1,80 @@
+use crate::{geo, Dataset, DatasetError, ZipCode};
+use polars::prelude::*;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrefixCentroid {
+    pub prefix: String,
+    pub zips: usize,
+    pub centroid: (f64, f64),
+    pub bounds: geo::BoundingBox,
+    /// Root mean square distance of the zips from the centroid
+    pub spread_km: f64,
+}
+
+impl Dataset {
+    /// All zips whose 5-digit code starts with `prefix`, e.g. `"021"`
+    pub fn with_prefix(&self, prefix: &str) -> Result<Dataset, DatasetError> {
+        let zips = self.dataframe().column("zip")?.u32()?;
+
+        let rows = zips
+            .into_iter()
+            .enumerate()
+            .filter_map(|(row, zip)| {
+                let zip = ZipCode::new(zip?).ok()?;
+                zip.to_string()
+                    .starts_with(prefix)
+                    .then_some(row as IdxSize)
+            })
+            .collect();
+
+        self.take_rows(rows)
+    }
+
+    /// Centroid, bounding box and spread of the zips sharing their first
+    /// `digits` digits, ordered by prefix. Three digits give the sectional
+    /// center facilities.
+    pub fn prefix_centroids(&self, digits: usize) -> Vec<PrefixCentroid> {
+        let digits = digits.min(5);
+
+        let mut prefixes = BTreeMap::<_, Vec<_>>::new();
+        for record in self.records() {
+            let prefix = record.zip.to_string()[..digits].to_string();
+            prefixes
+                .entry(prefix)
+                .or_default()
+                .push((record.latitude, record.longitude));
+        }
+
+        prefixes
+            .into_iter()
+            .filter_map(|(prefix, locations)| {
+                let centroid = geo::centroid(locations.iter().copied())?;
+                let squared_distances: f64 = locations
+                    .iter()
+                    .map(|&location| geo::haversine_distance(centroid, location).powi(2))
+                    .sum();
+
+                let bounds = locations.iter().fold(
+                    geo::BoundingBox::new(f64::MAX, f64::MIN, f64::MAX, f64::MIN),
+                    |bounds, &(lat, lon)| {
+                        geo::BoundingBox::new(
+                            bounds.min_lat.min(lat),
+                            bounds.max_lat.max(lat),
+                            bounds.min_lon.min(lon),
+                            bounds.max_lon.max(lon),
+                        )
+                    },
+                );
+
+                Some(PrefixCentroid {
+                    prefix,
+                    zips: locations.len(),
+                    centroid,
+                    bounds,
+                    spread_km: (squared_distances / locations.len() as f64).sqrt(),
+                })
+            })
+            .collect()
+    }
+}
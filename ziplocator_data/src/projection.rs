@@ -0,0 +1,199 @@
+use crate::{
+    geo::{self, WGS84_FLATTENING, WGS84_SEMI_MAJOR_AXIS_KM},
+    DatasetError,
+};
+use serde::{Deserialize, Serialize};
+use std::{f64::consts::FRAC_PI_4, fmt::Display, str::FromStr};
+
+/// Coordinate space a `(lat, lon)` point in degrees can be mapped into and
+/// back from. Planar projections are in kilometres.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Projection {
+    /// Plain WGS 84 degrees, `[lat, lon]`
+    #[default]
+    LatLon,
+    /// Spherical Mercator as used by web maps (EPSG:3857), `[x, y]`
+    WebMercator,
+    /// Albers Equal Area Conic for the contiguous US (EPSG:5070), `[x, y]`
+    AlbersConus,
+    /// Earth-centred, earth-fixed unit vector, `[x, y, z]`
+    Ecef,
+}
+
+impl Projection {
+    pub fn dimensions(self) -> usize {
+        match self {
+            Projection::Ecef => 3,
+            _ => 2,
+        }
+    }
+
+    /// Ground length of one unit of the projected space in kilometres, where
+    /// it is about the same everywhere. `None` for degrees and for Web
+    /// Mercator, whose units shrink to `cos(lat)` km away from the equator,
+    /// see [`web_mercator_scale`].
+    pub fn unit_km(self) -> Option<f64> {
+        match self {
+            Projection::LatLon | Projection::WebMercator => None,
+            Projection::AlbersConus => Some(1.0),
+            Projection::Ecef => Some(geo::EARTH_RADIUS_KM),
+        }
+    }
+
+    pub fn forward(self, lat: f64, lon: f64) -> Vec<f64> {
+        match self {
+            Projection::LatLon => vec![lat, lon],
+            Projection::WebMercator => {
+                let (x, y) = web_mercator(lat, lon);
+                vec![x, y]
+            }
+            Projection::AlbersConus => {
+                let (x, y) = albers_conus(lat, lon);
+                vec![x, y]
+            }
+            Projection::Ecef => ecef(lat, lon).to_vec(),
+        }
+    }
+
+    /// Maps projected coordinates back to `(lat, lon)`. Missing coordinates
+    /// are taken as zero.
+    pub fn inverse(self, coordinates: &[f64]) -> (f64, f64) {
+        let coordinate = |i| coordinates.get(i).copied().unwrap_or(0.0);
+        match self {
+            Projection::LatLon => (coordinate(0), coordinate(1)),
+            Projection::WebMercator => web_mercator_inverse(coordinate(0), coordinate(1)),
+            Projection::AlbersConus => albers_conus_inverse(coordinate(0), coordinate(1)),
+            Projection::Ecef => ecef_inverse([coordinate(0), coordinate(1), coordinate(2)]),
+        }
+    }
+}
+
+impl Display for Projection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Projection::LatLon => "latlon",
+            Projection::WebMercator => "web-mercator",
+            Projection::AlbersConus => "albers-conus",
+            Projection::Ecef => "ecef",
+        })
+    }
+}
+
+impl FromStr for Projection {
+    type Err = DatasetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "latlon" => Projection::LatLon,
+            "web-mercator" => Projection::WebMercator,
+            "albers-conus" => Projection::AlbersConus,
+            "ecef" => Projection::Ecef,
+            _ => return Err(DatasetError::UnknownProjection(s.into())),
+        })
+    }
+}
+
+pub fn web_mercator(lat: f64, lon: f64) -> (f64, f64) {
+    let lat = lat.clamp(-85.051_128_78, 85.051_128_78).to_radians();
+    (
+        WGS84_SEMI_MAJOR_AXIS_KM * lon.to_radians(),
+        WGS84_SEMI_MAJOR_AXIS_KM * (FRAC_PI_4 + lat / 2.0).tan().ln(),
+    )
+}
+
+/// How many projected kilometres one kilometre on the ground spans at the
+/// projected `y`, i.e. `sec(lat)`
+pub fn web_mercator_scale(y: f64) -> f64 {
+    (y / WGS84_SEMI_MAJOR_AXIS_KM).cosh()
+}
+
+pub fn web_mercator_inverse(x: f64, y: f64) -> (f64, f64) {
+    let lat = 2.0 * (y / WGS84_SEMI_MAJOR_AXIS_KM).exp().atan() - 2.0 * FRAC_PI_4;
+    (
+        lat.to_degrees(),
+        (x / WGS84_SEMI_MAJOR_AXIS_KM).to_degrees(),
+    )
+}
+
+/// Standard parallels, latitude of origin and central meridian of EPSG:5070
+const ALBERS_PARALLELS: (f64, f64) = (29.5, 45.5);
+const ALBERS_ORIGIN: (f64, f64) = (23.0, -96.0);
+
+/// Constants of the ellipsoidal Albers projection, see Snyder, "Map
+/// Projections: A Working Manual", pp. 101-102
+struct Albers {
+    e: f64,
+    n: f64,
+    c: f64,
+    rho0: f64,
+}
+
+impl Albers {
+    fn new() -> Self {
+        let e = (WGS84_FLATTENING * (2.0 - WGS84_FLATTENING)).sqrt();
+        let m = |lat: f64| {
+            let sin = lat.to_radians().sin();
+            lat.to_radians().cos() / (1.0 - e * e * sin * sin).sqrt()
+        };
+        let q = |lat: f64| albers_q(e, lat.to_radians().sin());
+
+        let (m1, m2) = (m(ALBERS_PARALLELS.0), m(ALBERS_PARALLELS.1));
+        let (q1, q2) = (q(ALBERS_PARALLELS.0), q(ALBERS_PARALLELS.1));
+        let n = (m1 * m1 - m2 * m2) / (q2 - q1);
+        let c = m1 * m1 + n * q1;
+        let rho0 = WGS84_SEMI_MAJOR_AXIS_KM * (c - n * q(ALBERS_ORIGIN.0)).sqrt() / n;
+
+        Self { e, n, c, rho0 }
+    }
+}
+
+fn albers_q(e: f64, sin: f64) -> f64 {
+    (1.0 - e * e)
+        * (sin / (1.0 - e * e * sin * sin)
+            - (1.0 / (2.0 * e)) * ((1.0 - e * sin) / (1.0 + e * sin)).ln())
+}
+
+pub fn albers_conus(lat: f64, lon: f64) -> (f64, f64) {
+    let Albers { e, n, c, rho0 } = Albers::new();
+
+    let q = albers_q(e, lat.to_radians().sin());
+    let rho = WGS84_SEMI_MAJOR_AXIS_KM * (c - n * q).max(0.0).sqrt() / n;
+    let theta = n * geo::normalize_longitude(lon - ALBERS_ORIGIN.1).to_radians();
+
+    (rho * theta.sin(), rho0 - rho * theta.cos())
+}
+
+pub fn albers_conus_inverse(x: f64, y: f64) -> (f64, f64) {
+    let Albers { e, n, c, rho0 } = Albers::new();
+
+    let rho = x.hypot(rho0 - y);
+    let theta = x.atan2(rho0 - y);
+    let q = (c - (rho * n / WGS84_SEMI_MAJOR_AXIS_KM).powi(2)) / n;
+
+    // Snyder's iteration for the latitude converges in a handful of steps
+    let mut lat = (q / 2.0).clamp(-1.0, 1.0).asin();
+    for _ in 0..10 {
+        let sin = lat.sin();
+        let one_minus = 1.0 - e * e * sin * sin;
+        lat += one_minus * one_minus / (2.0 * lat.cos())
+            * (q / (1.0 - e * e) - sin / one_minus
+                + (1.0 / (2.0 * e)) * ((1.0 - e * sin) / (1.0 + e * sin)).ln());
+    }
+
+    (
+        lat.to_degrees(),
+        geo::normalize_longitude(ALBERS_ORIGIN.1 + (theta / n).to_degrees()),
+    )
+}
+
+/// Unit vector from the earth's centre, with x towards `(0, 0)` and z towards
+/// the north pole
+pub fn ecef(lat: f64, lon: f64) -> [f64; 3] {
+    geo::unit_vector(lat, lon)
+}
+
+/// Accepts vectors of any length, e.g. unnormalized model outputs
+pub fn ecef_inverse([x, y, z]: [f64; 3]) -> (f64, f64) {
+    (z.atan2(x.hypot(y)).to_degrees(), y.atan2(x).to_degrees())
+}
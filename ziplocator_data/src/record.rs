@@ -0,0 +1,52 @@
+use crate::ZipCode;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZipRecord {
+    pub zip: ZipCode,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub city: String,
+    pub state_id: String,
+    pub state_name: String,
+    pub county_name: String,
+    pub population: Option<u64>,
+    pub density: Option<f64>,
+    pub timezone: String,
+}
+
+impl crate::Dataset {
+    pub fn record(&self, zip: ZipCode) -> Option<ZipRecord> {
+        self.record_at(self.zip_row(zip)?)
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = ZipRecord> + '_ {
+        (0..self.dataframe().height()).filter_map(|row| self.record_at(row))
+    }
+
+    fn record_at(&self, row: usize) -> Option<ZipRecord> {
+        let column = |name| self.dataframe().column(name).ok();
+        let string = |name| {
+            Some(
+                column(name)?
+                    .str()
+                    .ok()?
+                    .get(row)
+                    .unwrap_or_default()
+                    .to_string(),
+            )
+        };
+
+        Some(ZipRecord {
+            zip: ZipCode::new(column("zip")?.u32().ok()?.get(row)?).ok()?,
+            latitude: column("lat")?.f64().ok()?.get(row)?,
+            longitude: column("lng")?.f64().ok()?.get(row)?,
+            city: string("city")?,
+            state_id: string("state_id")?,
+            state_name: string("state_name")?,
+            county_name: string("county_name")?,
+            population: column("population")?.u64().ok()?.get(row),
+            density: column("density")?.f64().ok()?.get(row),
+            timezone: string("timezone")?,
+        })
+    }
+}
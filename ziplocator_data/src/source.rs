@@ -0,0 +1,380 @@
+use crate::{Dataset, DatasetError, ValidationPolicy};
+use hex_literal::hex;
+use polars::prelude::*;
+use sha2::{Digest, Sha256};
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{Cursor, Read},
+    path::Path,
+    str::FromStr,
+};
+
+pub const DATASET_ENV: &str = "ZIPLOCATOR_DATASET";
+
+/// A downloadable zip database: where to get it, the checksums pinning the
+/// exact vintage and how its columns map onto the dataset schema.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DatasetSource {
+    pub name: &'static str,
+    pub url: &'static str,
+    pub archive_sha256: [u8; 32],
+    pub path: &'static str,
+    pub sha256: [u8; 32],
+    pub format: SourceFormat,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SourceFormat {
+    pub separator: u8,
+    pub has_header: bool,
+    pub columns: ColumnMapping,
+}
+
+/// Source column names for each dataset column. Columns a source does not
+/// provide are filled with nulls.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColumnMapping {
+    pub zip: &'static str,
+    pub lat: &'static str,
+    pub lng: &'static str,
+    pub city: Option<&'static str>,
+    pub state_id: Option<&'static str>,
+    pub state_name: Option<&'static str>,
+    pub county_name: Option<&'static str>,
+    pub population: Option<&'static str>,
+    pub density: Option<&'static str>,
+    pub timezone: Option<&'static str>,
+}
+
+impl DatasetSource {
+    pub const SIMPLEMAPS_1_90: DatasetSource = DatasetSource {
+        name: "simplemaps-1.90",
+        url:
+            "https://simplemaps.com/static/data/us-zips/1.90/basic/simplemaps_uszips_basicv1.90.zip",
+        archive_sha256: hex!("911765CB2433F7BDFF22D2817CA1B96BDE8F4B6F5C10FB9AEA1F3310DC04F1F8"),
+        path: "uszips.csv",
+        sha256: hex!("0B8F9D378D8868F42324788A457A17434E38BB364060055D5C338A2FFE512285"),
+        format: SourceFormat::SIMPLEMAPS,
+    };
+
+    /// Sources with pinned checksums, selectable by name through
+    /// `$ZIPLOCATOR_DATASET`. Only simplemaps 1.90 is pinned so far; other
+    /// vintages can be described with [`DatasetSource::simplemaps`],
+    /// [`DatasetSource::census_zcta_gazetteer`] and
+    /// [`DatasetSource::geonames_us`] once their checksums are known. Until
+    /// then, local copies in any of the [`SourceFormat`]s are read unverified
+    /// with [`SourceFormat::load_path`].
+    pub const KNOWN: &'static [DatasetSource] = &[Self::SIMPLEMAPS_1_90];
+
+    pub fn find(name: &str) -> Option<DatasetSource> {
+        Self::KNOWN
+            .iter()
+            .find(|source| source.name.eq_ignore_ascii_case(name))
+            .copied()
+    }
+
+    pub const fn simplemaps(
+        name: &'static str,
+        url: &'static str,
+        archive_sha256: [u8; 32],
+        sha256: [u8; 32],
+    ) -> Self {
+        Self {
+            name,
+            url,
+            archive_sha256,
+            path: "uszips.csv",
+            sha256,
+            format: SourceFormat::SIMPLEMAPS,
+        }
+    }
+
+    /// The national ZCTA file of a yearly Census Gazetteer release, e.g.
+    /// `2023_Gaz_zcta_national.txt` inside `2023_Gaz_zcta_national.zip`.
+    pub const fn census_zcta_gazetteer(
+        name: &'static str,
+        url: &'static str,
+        archive_sha256: [u8; 32],
+        path: &'static str,
+        sha256: [u8; 32],
+    ) -> Self {
+        Self {
+            name,
+            url,
+            archive_sha256,
+            path,
+            sha256,
+            format: SourceFormat::CENSUS_ZCTA_GAZETTEER,
+        }
+    }
+
+    /// GeoNames publishes a single, regularly updated `US.zip`, so every
+    /// snapshot needs its own checksums.
+    pub const fn geonames_us(
+        name: &'static str,
+        archive_sha256: [u8; 32],
+        sha256: [u8; 32],
+    ) -> Self {
+        Self {
+            name,
+            url: "https://download.geonames.org/export/zip/US.zip",
+            archive_sha256,
+            path: "US.txt",
+            sha256,
+            format: SourceFormat::GEONAMES,
+        }
+    }
+
+    /// Reads a local copy of the archive or the file inside it. Rows failing
+    /// validation are dropped, see [`crate::DatasetLoader::path`] for other
+    /// policies.
+    pub fn load_path(&self, path: impl AsRef<Path>) -> Result<Dataset, DatasetError> {
+        self.load_reader(File::open(path)?)
+    }
+
+    pub fn load_reader(&self, reader: impl Read) -> Result<Dataset, DatasetError> {
+        let dataset = self.parse(self.read_verified(reader)?)?;
+        Ok(dataset.validate(ValidationPolicy::default())?.0)
+    }
+
+    pub(crate) fn parse_path(&self, path: impl AsRef<Path>) -> Result<Dataset, DatasetError> {
+        self.parse(self.read_verified(File::open(path)?)?)
+    }
+
+    pub(crate) fn parse(&self, data: Vec<u8>) -> Result<Dataset, DatasetError> {
+        Dataset::from_dataframe(self.format.parse(data)?)
+    }
+
+    /// Reads a local copy of either the archive or the file inside it,
+    /// verifying it against the pinned checksums.
+    fn read_verified(&self, mut reader: impl Read) -> Result<Vec<u8>, DatasetError> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let hash: [u8; 32] = Sha256::digest(&data).into();
+        if hash == self.sha256 {
+            return Ok(data);
+        }
+        if hash != self.archive_sha256 {
+            return Err(self.checksum_mismatch(hash));
+        }
+
+        let mut archive = ::zip::ZipArchive::new(Cursor::new(data))?;
+        let mut file = Vec::new();
+        archive.by_name(self.path)?.read_to_end(&mut file)?;
+
+        let hash: [u8; 32] = Sha256::digest(&file).into();
+        if hash == self.sha256 {
+            Ok(file)
+        } else {
+            Err(self.checksum_mismatch(hash))
+        }
+    }
+
+    fn checksum_mismatch(&self, actual: [u8; 32]) -> DatasetError {
+        let hex_string = |bytes: &[u8]| bytes.iter().map(|byte| format!("{byte:02X}")).collect();
+        DatasetError::Checksum {
+            expected: hex_string(&self.sha256),
+            actual: hex_string(&actual),
+        }
+    }
+}
+
+impl Default for DatasetSource {
+    fn default() -> Self {
+        Self::SIMPLEMAPS_1_90
+    }
+}
+
+impl SourceFormat {
+    pub const SIMPLEMAPS: SourceFormat = SourceFormat {
+        separator: b',',
+        has_header: true,
+        columns: ColumnMapping {
+            zip: "zip",
+            lat: "lat",
+            lng: "lng",
+            city: Some("city"),
+            state_id: Some("state_id"),
+            state_name: Some("state_name"),
+            county_name: Some("county_name"),
+            population: Some("population"),
+            density: Some("density"),
+            timezone: Some("timezone"),
+        },
+    };
+
+    pub const CENSUS_ZCTA_GAZETTEER: SourceFormat = SourceFormat {
+        separator: b'\t',
+        has_header: true,
+        columns: ColumnMapping {
+            zip: "GEOID",
+            lat: "INTPTLAT",
+            lng: "INTPTLONG",
+            city: None,
+            state_id: None,
+            state_name: None,
+            county_name: None,
+            population: None,
+            density: None,
+            timezone: None,
+        },
+    };
+
+    /// GeoNames postal code dumps have no header row, see
+    /// <https://download.geonames.org/export/zip/readme.txt>.
+    pub const GEONAMES: SourceFormat = SourceFormat {
+        separator: b'\t',
+        has_header: false,
+        columns: ColumnMapping {
+            zip: "column_2",
+            lat: "column_10",
+            lng: "column_11",
+            city: Some("column_3"),
+            state_id: Some("column_5"),
+            state_name: Some("column_4"),
+            county_name: Some("column_6"),
+            population: None,
+            density: None,
+            timezone: None,
+        },
+    };
+
+    /// Parses an extracted file without checksum verification, e.g. a newer
+    /// upstream release that has not been pinned yet. Rows failing validation
+    /// are dropped, see [`crate::DatasetLoader::format`] for other policies.
+    pub fn load_path(&self, path: impl AsRef<Path>) -> Result<Dataset, DatasetError> {
+        Ok(self
+            .parse_path(path)?
+            .validate(ValidationPolicy::default())?
+            .0)
+    }
+
+    pub(crate) fn parse_path(&self, path: impl AsRef<Path>) -> Result<Dataset, DatasetError> {
+        Dataset::from_dataframe(self.parse(std::fs::read(path)?)?)
+    }
+
+    fn parse(&self, data: Vec<u8>) -> Result<DataFrame, DatasetError> {
+        let columns = self.columns.dataset_columns();
+
+        let nullable_columns = [
+            (Some(self.columns.lat), DataType::Float64),
+            (Some(self.columns.lng), DataType::Float64),
+            (self.columns.population, DataType::Int64),
+            (self.columns.density, DataType::Float64),
+        ]
+        .into_iter()
+        .filter_map(|(source_column, dtype)| Some(Field::new(source_column?.into(), dtype)));
+
+        let dataframe = CsvReadOptions::default()
+            .with_has_header(self.has_header)
+            .with_schema_overwrite(Some(Arc::new(Schema::from_iter(nullable_columns))))
+            .with_parse_options(CsvParseOptions::default().with_separator(self.separator))
+            .into_reader_with_file_handle(Cursor::new(self.trim_padding(&data)))
+            .finish()
+            .map_err(DatasetError::CsvParse)?;
+
+        let mut selection = Vec::with_capacity(columns.len());
+        for (column, source_column, expected) in columns {
+            let Some(source_column) = source_column else {
+                selection.push(lit(NULL).cast(expected).alias(column));
+                continue;
+            };
+
+            let actual = dataframe
+                .column(source_column)
+                .map_err(|_| DatasetError::MissingColumn(source_column))?
+                .dtype();
+
+            let matches = match expected {
+                DataType::Float64 => actual.is_numeric(),
+                ref expected if expected.is_integer() => actual.is_integer(),
+                ref expected => actual == expected,
+            };
+            if !matches {
+                return Err(DatasetError::TypeMismatch {
+                    column,
+                    expected,
+                    actual: actual.clone(),
+                });
+            }
+
+            selection.push(col(source_column).cast(expected).alias(column));
+        }
+
+        Ok(dataframe.lazy().select(selection).collect()?)
+    }
+
+    /// Census Gazetteer files pad their last column with spaces, header
+    /// included. Header names and line ends are trimmed before parsing so the
+    /// schema matches the padded columns. Tabs are kept, as trailing ones
+    /// separate empty columns.
+    fn trim_padding(&self, data: &[u8]) -> Vec<u8> {
+        let mut trimmed = Vec::with_capacity(data.len());
+        for (index, mut line) in data.split(|&byte| byte == b'\n').enumerate() {
+            if index > 0 {
+                trimmed.push(b'\n');
+            }
+            if index == 0 && self.has_header {
+                let names = line
+                    .split(|&byte| byte == self.separator)
+                    .map(|name| name.trim_ascii());
+                for (column, name) in names.enumerate() {
+                    if column > 0 {
+                        trimmed.push(self.separator);
+                    }
+                    trimmed.extend_from_slice(name);
+                }
+            } else {
+                while let [rest @ .., b' ' | b'\r'] = line {
+                    line = rest;
+                }
+                trimmed.extend_from_slice(line);
+            }
+        }
+
+        trimmed
+    }
+}
+
+impl Display for SourceFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match *self {
+            SourceFormat::SIMPLEMAPS => "simplemaps",
+            SourceFormat::CENSUS_ZCTA_GAZETTEER => "census",
+            SourceFormat::GEONAMES => "geonames",
+            _ => "custom",
+        })
+    }
+}
+
+impl FromStr for SourceFormat {
+    type Err = DatasetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "simplemaps" => Ok(SourceFormat::SIMPLEMAPS),
+            "census" => Ok(SourceFormat::CENSUS_ZCTA_GAZETTEER),
+            "geonames" => Ok(SourceFormat::GEONAMES),
+            _ => Err(DatasetError::UnknownSourceFormat(s.to_string())),
+        }
+    }
+}
+
+impl ColumnMapping {
+    fn dataset_columns(&self) -> [(&'static str, Option<&'static str>, DataType); 10] {
+        [
+            ("zip", Some(self.zip), DataType::UInt32),
+            ("lat", Some(self.lat), DataType::Float64),
+            ("lng", Some(self.lng), DataType::Float64),
+            ("city", self.city, DataType::String),
+            ("state_id", self.state_id, DataType::String),
+            ("state_name", self.state_name, DataType::String),
+            ("county_name", self.county_name, DataType::String),
+            ("population", self.population, DataType::UInt64),
+            ("density", self.density, DataType::Float64),
+            ("timezone", self.timezone, DataType::String),
+        ]
+    }
+}
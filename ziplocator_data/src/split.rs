@@ -0,0 +1,240 @@
+use crate::{Dataset, DatasetError};
+use polars::prelude::*;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZipGroup {
+    State,
+    /// The first three digits of the zip code
+    Prefix,
+}
+
+/// Serialized by the same names it is parsed from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum SplitStrategy {
+    /// Rows are shuffled without regard to where they are
+    Random,
+    /// Every group is split separately so each partition keeps the dataset's
+    /// mix of groups
+    Stratified(ZipGroup),
+    /// Whole groups end up in a single partition, so evaluation measures
+    /// regions the model has never seen
+    HoldOut(ZipGroup),
+    /// Each partition is one contiguous range of zip codes
+    ZipRange,
+}
+
+impl Dataset {
+    /// Shuffles the rows with `seed` and partitions them into disjoint
+    /// datasets sized by `ratios`, e.g. `[0.8, 0.1, 0.1]` for train,
+    /// validation and test.
+    pub fn split<const N: usize>(
+        &self,
+        ratios: [f64; N],
+        seed: u64,
+    ) -> Result<[Dataset; N], DatasetError> {
+        self.split_with(ratios, seed, SplitStrategy::Random)
+    }
+
+    pub fn split_stratified<const N: usize>(
+        &self,
+        ratios: [f64; N],
+        seed: u64,
+        group: ZipGroup,
+    ) -> Result<[Dataset; N], DatasetError> {
+        self.split_with(ratios, seed, SplitStrategy::Stratified(group))
+    }
+
+    /// Fails if a partition with a non-zero ratio would be empty, e.g. when
+    /// holding out whole states of a single state.
+    pub fn split_with<const N: usize>(
+        &self,
+        ratios: [f64; N],
+        seed: u64,
+        strategy: SplitStrategy,
+    ) -> Result<[Dataset; N], DatasetError> {
+        let total: f64 = ratios.iter().sum();
+        if N == 0
+            || ratios
+                .iter()
+                .any(|ratio| !ratio.is_finite() || *ratio < 0.0)
+            || total <= 0.0
+        {
+            return Err(DatasetError::InvalidSplit(format!(
+                "ratios {ratios:?} must be non-negative and sum to more than zero"
+            )));
+        }
+        let cumulative: [f64; N] =
+            std::array::from_fn(|part| ratios[..=part].iter().sum::<f64>() / total);
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut parts: [Vec<IdxSize>; N] = std::array::from_fn(|_| Vec::new());
+
+        match strategy {
+            SplitStrategy::Random => {
+                let mut rows: Vec<_> = (0..self.len() as IdxSize).collect();
+                rows.shuffle(&mut rng);
+                split_rows(&rows, &cumulative, parts.iter_mut());
+            }
+            SplitStrategy::Stratified(group) => {
+                for mut rows in self.group_rows(group)?.into_values() {
+                    rows.shuffle(&mut rng);
+                    split_rows(&rows, &cumulative, parts.iter_mut());
+                }
+            }
+            SplitStrategy::HoldOut(group) => {
+                let mut groups: Vec<_> = self.group_rows(group)?.into_values().collect();
+                groups.shuffle(&mut rng);
+
+                // Assign each group to the partition its middle row falls into
+                let mut assigned = 0;
+                for rows in groups {
+                    let middle = (assigned as f64 + rows.len() as f64 / 2.0) / self.len() as f64;
+                    let part = cumulative
+                        .iter()
+                        .position(|&bound| middle < bound)
+                        .unwrap_or(N - 1);
+                    assigned += rows.len();
+                    parts[part].extend(rows);
+                }
+            }
+            SplitStrategy::ZipRange => {
+                let zips = self.dataframe().column("zip")?.u32()?;
+                let mut rows: Vec<_> = (0..self.len() as IdxSize).collect();
+                rows.sort_by_key(|&row| zips.get(row as usize));
+
+                // Shuffle which partition gets which range, so the held out
+                // range is not always the highest zips
+                let mut order: Vec<_> = parts.iter_mut().zip(ratios).collect();
+                order.shuffle(&mut rng);
+                let mut sum = 0.0;
+                let cumulative: Vec<_> = order
+                    .iter()
+                    .map(|(_, ratio)| {
+                        sum += ratio;
+                        sum / total
+                    })
+                    .collect();
+                split_rows(&rows, &cumulative, order.into_iter().map(|(part, _)| part));
+            }
+        }
+
+        if let Some(part) = (0..N).find(|&part| ratios[part] > 0.0 && parts[part].is_empty()) {
+            return Err(DatasetError::InvalidSplit(format!(
+                "partition {part} of a {strategy} split of {} rows with ratios {ratios:?} \
+                 would be empty",
+                self.len()
+            )));
+        }
+
+        let datasets = parts
+            .into_iter()
+            .map(|rows| self.take_rows(rows))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match datasets.try_into() {
+            Ok(datasets) => Ok(datasets),
+            Err(_) => unreachable!("one dataset per ratio"),
+        }
+    }
+
+    fn group_rows(&self, group: ZipGroup) -> Result<BTreeMap<String, Vec<IdxSize>>, DatasetError> {
+        let keys: Vec<String> = match group {
+            ZipGroup::State => self
+                .dataframe()
+                .column("state_id")?
+                .str()?
+                .into_iter()
+                .map(|state| state.unwrap_or_default().to_string())
+                .collect(),
+            ZipGroup::Prefix => self
+                .dataframe()
+                .column("zip")?
+                .u32()?
+                .into_iter()
+                .map(|zip| {
+                    zip.map(|zip| format!("{:03}", zip / 100))
+                        .unwrap_or_default()
+                })
+                .collect(),
+        };
+
+        let mut groups = BTreeMap::<_, Vec<IdxSize>>::new();
+        for (row, key) in keys.into_iter().enumerate() {
+            groups.entry(key).or_default().push(row as IdxSize);
+        }
+
+        Ok(groups)
+    }
+}
+
+/// Cuts `rows` into consecutive chunks ending at the `cumulative` fractions.
+fn split_rows<'a>(
+    rows: &[IdxSize],
+    cumulative: &[f64],
+    parts: impl Iterator<Item = &'a mut Vec<IdxSize>>,
+) {
+    let mut start = 0;
+    for (part, bound) in parts.zip(cumulative) {
+        let end = ((rows.len() as f64 * bound).round() as usize).clamp(start, rows.len());
+        part.extend_from_slice(&rows[start..end]);
+        start = end;
+    }
+}
+
+impl Default for SplitStrategy {
+    fn default() -> Self {
+        SplitStrategy::Stratified(ZipGroup::State)
+    }
+}
+
+impl Display for SplitStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SplitStrategy::Random => "random",
+            SplitStrategy::Stratified(ZipGroup::State) => "stratified-state",
+            SplitStrategy::Stratified(ZipGroup::Prefix) => "stratified-prefix",
+            SplitStrategy::HoldOut(ZipGroup::State) => "holdout-state",
+            SplitStrategy::HoldOut(ZipGroup::Prefix) => "holdout-prefix",
+            SplitStrategy::ZipRange => "zip-range",
+        })
+    }
+}
+
+impl From<SplitStrategy> for String {
+    fn from(strategy: SplitStrategy) -> Self {
+        strategy.to_string()
+    }
+}
+
+impl TryFrom<String> for SplitStrategy {
+    type Error = DatasetError;
+
+    fn try_from(strategy: String) -> Result<Self, Self::Error> {
+        strategy.parse()
+    }
+}
+
+impl FromStr for SplitStrategy {
+    type Err = DatasetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "random" => SplitStrategy::Random,
+            "stratified-state" => SplitStrategy::Stratified(ZipGroup::State),
+            "stratified-prefix" => SplitStrategy::Stratified(ZipGroup::Prefix),
+            "holdout-state" => SplitStrategy::HoldOut(ZipGroup::State),
+            "holdout-prefix" => SplitStrategy::HoldOut(ZipGroup::Prefix),
+            "zip-range" => SplitStrategy::ZipRange,
+            _ => {
+                return Err(DatasetError::InvalidSplit(format!(
+                    "unknown strategy \"{s}\", expected one of random, stratified-state, \
+                     stratified-prefix, holdout-state, holdout-prefix or zip-range"
+                )))
+            }
+        })
+    }
+}
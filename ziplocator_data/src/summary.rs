@@ -0,0 +1,90 @@
+use crate::{geo, Dataset, DatasetError, ZipCode};
+use polars::prelude::*;
+use std::{collections::BTreeMap, fmt::Display};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DatasetSummary {
+    pub rows: usize,
+    pub bounds: Option<geo::BoundingBox>,
+    pub lat_mean: f64,
+    pub lat_std: f64,
+    pub lng_mean: f64,
+    pub lng_std: f64,
+    pub state_counts: BTreeMap<String, usize>,
+    pub zip_range: Option<(ZipCode, ZipCode)>,
+    /// Bits needed to represent the largest zip code
+    pub zip_bits: u32,
+}
+
+impl Dataset {
+    pub fn summary(&self) -> Result<DatasetSummary, DatasetError> {
+        let zips = self.dataframe().column("zip")?.u32()?;
+        let lats = self.dataframe().column("lat")?.f64()?;
+        let lngs = self.dataframe().column("lng")?.f64()?;
+
+        let bounds = match (lats.min(), lats.max(), lngs.min(), lngs.max()) {
+            (Some(min_lat), Some(max_lat), Some(min_lng), Some(max_lng)) => {
+                Some(geo::BoundingBox::new(min_lat, max_lat, min_lng, max_lng))
+            }
+            _ => None,
+        };
+
+        let mut state_counts = BTreeMap::new();
+        for state in self.dataframe().column("state_id")?.str()? {
+            *state_counts
+                .entry(state.unwrap_or_default().to_string())
+                .or_default() += 1;
+        }
+
+        let zip_range = zips
+            .min()
+            .zip(zips.max())
+            .and_then(|(min, max)| Some((ZipCode::new(min).ok()?, ZipCode::new(max).ok()?)));
+
+        Ok(DatasetSummary {
+            rows: self.len(),
+            bounds,
+            lat_mean: lats.mean().unwrap_or(f64::NAN),
+            lat_std: lats.std(1).unwrap_or(f64::NAN),
+            lng_mean: lngs.mean().unwrap_or(f64::NAN),
+            lng_std: lngs.std(1).unwrap_or(f64::NAN),
+            state_counts,
+            zip_range,
+            zip_bits: zips.max().map_or(0, |max| u32::BITS - max.leading_zeros()),
+        })
+    }
+}
+
+impl Display for DatasetSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Rows: {}", self.rows)?;
+        if let Some(bounds) = self.bounds {
+            writeln!(
+                f,
+                "Bounds: lat {} to {}, lng {} to {}",
+                bounds.min_lat, bounds.max_lat, bounds.min_lon, bounds.max_lon
+            )?;
+        }
+        writeln!(
+            f,
+            "Latitude: mean {:.4}, std {:.4}",
+            self.lat_mean, self.lat_std
+        )?;
+        writeln!(
+            f,
+            "Longitude: mean {:.4}, std {:.4}",
+            self.lng_mean, self.lng_std
+        )?;
+        if let Some((min, max)) = self.zip_range {
+            writeln!(f, "Zip range: {min} to {max}")?;
+        }
+        writeln!(f, "Zip bit width: {}", self.zip_bits)?;
+
+        write!(f, "States:")?;
+        for (state, count) in &self.state_counts {
+            write!(f, "\n  {state}: {count}")?;
+        }
+
+        Ok(())
+    }
+}
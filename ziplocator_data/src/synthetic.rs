@@ -0,0 +1,244 @@
+use crate::{geo, Dataset, ZipCode};
+use polars::prelude::*;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use std::collections::BTreeMap;
+
+/// State id, name, 3-digit prefix range, rough center and timezone
+type SyntheticState = (
+    &'static str,
+    &'static str,
+    (u32, u32),
+    (f64, f64),
+    &'static str,
+);
+
+/// Two states per leading digit, so prefixes sweep across the country like
+/// real zips do.
+const STATES: &[SyntheticState] = &[
+    (
+        "PR",
+        "Puerto Rico",
+        (6, 9),
+        (18.2, -66.5),
+        "America/Puerto_Rico",
+    ),
+    (
+        "MA",
+        "Massachusetts",
+        (10, 27),
+        (42.3, -71.8),
+        "America/New_York",
+    ),
+    (
+        "NY",
+        "New York",
+        (100, 149),
+        (42.5, -75.5),
+        "America/New_York",
+    ),
+    (
+        "PA",
+        "Pennsylvania",
+        (150, 196),
+        (40.9, -77.6),
+        "America/New_York",
+    ),
+    (
+        "VA",
+        "Virginia",
+        (220, 246),
+        (37.5, -78.8),
+        "America/New_York",
+    ),
+    (
+        "NC",
+        "North Carolina",
+        (270, 289),
+        (35.5, -79.4),
+        "America/New_York",
+    ),
+    (
+        "GA",
+        "Georgia",
+        (300, 319),
+        (32.7, -83.4),
+        "America/New_York",
+    ),
+    (
+        "FL",
+        "Florida",
+        (320, 349),
+        (28.6, -82.4),
+        "America/New_York",
+    ),
+    ("OH", "Ohio", (430, 458), (40.3, -82.8), "America/New_York"),
+    (
+        "MI",
+        "Michigan",
+        (480, 499),
+        (43.6, -84.7),
+        "America/Detroit",
+    ),
+    ("IA", "Iowa", (500, 528), (42.0, -93.5), "America/Chicago"),
+    (
+        "MN",
+        "Minnesota",
+        (550, 567),
+        (46.0, -94.3),
+        "America/Chicago",
+    ),
+    (
+        "MO",
+        "Missouri",
+        (630, 658),
+        (38.4, -92.5),
+        "America/Chicago",
+    ),
+    ("KS", "Kansas", (660, 679), (38.5, -98.4), "America/Chicago"),
+    (
+        "LA",
+        "Louisiana",
+        (700, 714),
+        (31.0, -92.0),
+        "America/Chicago",
+    ),
+    ("TX", "Texas", (750, 799), (31.2, -99.3), "America/Chicago"),
+    (
+        "CO",
+        "Colorado",
+        (800, 816),
+        (39.0, -105.5),
+        "America/Denver",
+    ),
+    (
+        "AZ",
+        "Arizona",
+        (850, 865),
+        (34.2, -111.7),
+        "America/Phoenix",
+    ),
+    (
+        "CA",
+        "California",
+        (900, 961),
+        (37.0, -119.5),
+        "America/Los_Angeles",
+    ),
+    (
+        "WA",
+        "Washington",
+        (980, 994),
+        (47.4, -120.5),
+        "America/Los_Angeles",
+    ),
+];
+
+/// Degrees a state's prefixes spread along its axis, and zips around their
+/// prefix
+const STATE_SPAN: f64 = 3.0;
+const ZIP_SPREAD: f64 = 0.15;
+
+/// Tries per zip before its location is pulled all the way to the state's
+/// center, which is always inside the US
+const PLACEMENT_ATTEMPTS: usize = 8;
+
+impl Dataset {
+    /// A random but plausible dataset with the real schema, for running
+    /// without a download. Zips cluster around their 3-digit prefix and
+    /// prefixes move steadily across their state. All rows pass validation.
+    /// `n` is capped at the number of zips the synthetic states can hold.
+    pub fn synthetic(n: usize, seed: u64) -> Dataset {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let axes: Vec<f64> = STATES
+            .iter()
+            .map(|_| rng.gen_range(0.0..std::f64::consts::PI))
+            .collect();
+
+        let mut candidates: Vec<_> = STATES
+            .iter()
+            .enumerate()
+            .flat_map(|(state, (_, _, (first, last), _, _))| {
+                (*first..=*last).flat_map(move |prefix| {
+                    (0..100).filter_map(move |suffix| {
+                        Some((ZipCode::new(prefix * 100 + suffix).ok()?, state, prefix))
+                    })
+                })
+            })
+            .collect();
+        candidates.shuffle(&mut rng);
+        candidates.truncate(n);
+
+        let mut zips = BTreeMap::new();
+        for (zip, state, prefix) in candidates {
+            let (_, _, (first, last), (lat, lng), _) = STATES[state];
+            let along = ((prefix - first) as f64 / (last - first).max(1) as f64 - 0.5) * STATE_SPAN;
+
+            // Prefixes at the ends of small states such as Puerto Rico can
+            // fall outside the US, so they are moved towards the center
+            let (lat, lng) = (0..PLACEMENT_ATTEMPTS)
+                .map(|attempt| {
+                    let scale = 1.0 - attempt as f64 / PLACEMENT_ATTEMPTS as f64;
+                    (
+                        lat + scale * (along * axes[state].sin() + normal(&mut rng) * ZIP_SPREAD),
+                        lng + scale * (along * axes[state].cos() + normal(&mut rng) * ZIP_SPREAD),
+                    )
+                })
+                .find(|&(lat, lng)| geo::in_us(lat, lng))
+                .unwrap_or((lat, lng));
+            zips.insert(zip, (state, prefix, lat, lng));
+        }
+
+        let rows = zips.len();
+        let (mut zip, mut lat, mut lng) = (
+            Vec::with_capacity(rows),
+            Vec::with_capacity(rows),
+            Vec::with_capacity(rows),
+        );
+        let (mut city, mut state_id, mut state_name, mut county_name, mut timezone) = (
+            Vec::with_capacity(rows),
+            Vec::with_capacity(rows),
+            Vec::with_capacity(rows),
+            Vec::with_capacity(rows),
+            Vec::with_capacity(rows),
+        );
+        let (mut population, mut density) = (Vec::with_capacity(rows), Vec::with_capacity(rows));
+
+        for (code, (state, prefix, latitude, longitude)) in zips {
+            let (id, name, _, _, zone) = STATES[state];
+            zip.push(code.get());
+            lat.push(latitude);
+            lng.push(longitude);
+            city.push(format!("Synthetic {code}"));
+            state_id.push(id);
+            state_name.push(name);
+            county_name.push(format!("County {prefix:03}"));
+            timezone.push(zone);
+
+            let people = rng.gen_range(100..50_000u64);
+            population.push(people);
+            density.push(people as f64 / rng.gen_range(1.0..500.0));
+        }
+
+        let dataframe = df! {
+            "zip" => zip,
+            "lat" => lat,
+            "lng" => lng,
+            "city" => city,
+            "state_id" => state_id,
+            "state_name" => state_name,
+            "county_name" => county_name,
+            "population" => population,
+            "density" => density,
+            "timezone" => timezone,
+        }
+        .expect("Synthetic columns have equal lengths");
+
+        Dataset::from_dataframe(dataframe).expect("Synthetic dataframe has the dataset schema")
+    }
+}
+
+/// Standard normal sample via Box-Muller
+fn normal(rng: &mut impl Rng) -> f64 {
+    let (u, v): (f64, f64) = (1.0 - rng.gen::<f64>(), rng.gen());
+    (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+}
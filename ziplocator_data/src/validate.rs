@@ -0,0 +1,155 @@
+use crate::{geo, Dataset, DatasetError, ZipCode};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fmt::Display, str::FromStr};
+
+/// What to do with rows that fail validation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ValidationPolicy {
+    #[default]
+    Drop,
+    Keep,
+    Error,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ValidationIssue {
+    InvalidZip,
+    MissingCoordinates,
+    CoordinatesOutOfRange,
+    OutsideUs,
+    /// A later row for a zip that already has a valid row
+    DuplicateZip,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidRow {
+    pub row: usize,
+    pub zip: Option<u32>,
+    pub issue: ValidationIssue,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub rows: usize,
+    pub invalid: Vec<InvalidRow>,
+    pub dropped: usize,
+}
+
+impl Dataset {
+    pub fn validate(
+        self,
+        policy: ValidationPolicy,
+    ) -> Result<(Dataset, ValidationReport), DatasetError> {
+        let zips = self.dataframe().column("zip")?.u32()?;
+        let lats = self.dataframe().column("lat")?.f64()?;
+        let lngs = self.dataframe().column("lng")?.f64()?;
+
+        let mut seen = HashSet::with_capacity(self.len());
+        let mut valid = Vec::with_capacity(self.len());
+        let mut invalid = Vec::new();
+
+        for (row, ((zip, lat), lng)) in zips.into_iter().zip(lats).zip(lngs).enumerate() {
+            let issue = match (zip.map(ZipCode::new), lat, lng) {
+                (None | Some(Err(_)), _, _) => Some(ValidationIssue::InvalidZip),
+                (_, None, _) | (_, _, None) => Some(ValidationIssue::MissingCoordinates),
+                (_, Some(lat), Some(lng))
+                    if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lng) =>
+                {
+                    Some(ValidationIssue::CoordinatesOutOfRange)
+                }
+                (_, Some(lat), Some(lng)) if !geo::in_us(lat, lng) => {
+                    Some(ValidationIssue::OutsideUs)
+                }
+                (Some(Ok(zip)), _, _) if !seen.insert(zip) => Some(ValidationIssue::DuplicateZip),
+                _ => None,
+            };
+
+            match issue {
+                Some(issue) => invalid.push(InvalidRow { row, zip, issue }),
+                None => valid.push(row as IdxSize),
+            }
+        }
+
+        let mut report = ValidationReport {
+            rows: self.len(),
+            invalid,
+            dropped: 0,
+        };
+
+        match policy {
+            _ if report.is_clean() => Ok((self, report)),
+            ValidationPolicy::Keep => Ok((self, report)),
+            ValidationPolicy::Error => Err(DatasetError::Validation(report)),
+            ValidationPolicy::Drop => {
+                report.dropped = report.invalid.len();
+                Ok((self.take_rows(valid)?, report))
+            }
+        }
+    }
+}
+
+impl ValidationReport {
+    pub fn is_clean(&self) -> bool {
+        self.invalid.is_empty()
+    }
+
+    pub fn count(&self, issue: ValidationIssue) -> usize {
+        self.invalid.iter().filter(|row| row.issue == issue).count()
+    }
+}
+
+impl Display for ValidationPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ValidationPolicy::Drop => "drop",
+            ValidationPolicy::Keep => "keep",
+            ValidationPolicy::Error => "error",
+        })
+    }
+}
+
+impl FromStr for ValidationPolicy {
+    type Err = DatasetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "drop" => Ok(ValidationPolicy::Drop),
+            "keep" => Ok(ValidationPolicy::Keep),
+            "error" => Ok(ValidationPolicy::Error),
+            _ => Err(DatasetError::UnknownValidationPolicy(s.to_string())),
+        }
+    }
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ValidationIssue::InvalidZip => "invalid zip",
+            ValidationIssue::MissingCoordinates => "missing coordinates",
+            ValidationIssue::CoordinatesOutOfRange => "coordinates out of range",
+            ValidationIssue::OutsideUs => "outside the US",
+            ValidationIssue::DuplicateZip => "duplicate zip",
+        })
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} rows checked", self.rows)?;
+        for issue in [
+            ValidationIssue::InvalidZip,
+            ValidationIssue::MissingCoordinates,
+            ValidationIssue::CoordinatesOutOfRange,
+            ValidationIssue::OutsideUs,
+            ValidationIssue::DuplicateZip,
+        ] {
+            let count = self.count(issue);
+            if count > 0 {
+                write!(f, ", {count} {issue}")?;
+            }
+        }
+        write!(f, ", {} dropped", self.dropped)
+    }
+}
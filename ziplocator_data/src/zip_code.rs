@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, str::FromStr};
+
+/// A 5-digit US zip code in `00001..=99999`, displayed with leading zeros.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "u32", into = "u32")]
+pub struct ZipCode(u32);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ZipCodeError {
+    InvalidFormat(String),
+    OutOfRange(u32),
+}
+
+impl ZipCode {
+    pub const MIN: u32 = 1;
+    pub const MAX: u32 = 99_999;
+    /// Bits needed to represent any zip code
+    pub const BITS: u32 = u32::BITS - Self::MAX.leading_zeros();
+
+    pub fn new(zip: u32) -> Result<Self, ZipCodeError> {
+        if (Self::MIN..=Self::MAX).contains(&zip) {
+            Ok(Self(zip))
+        } else {
+            Err(ZipCodeError::OutOfRange(zip))
+        }
+    }
+
+    pub fn get(self) -> u32 {
+        self.0
+    }
+}
+
+impl Display for ZipCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:05}", self.0)
+    }
+}
+
+/// Accepts `02134` as well as ZIP+4 codes like `02134-1234`.
+impl FromStr for ZipCode {
+    type Err = ZipCodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || ZipCodeError::InvalidFormat(s.to_string());
+
+        let (zip, plus_four) = match s.split_once('-') {
+            Some((zip, plus_four)) => (zip, Some(plus_four)),
+            None => (s, None),
+        };
+
+        let is_digits =
+            |part: &str, len| part.len() == len && part.bytes().all(|b| b.is_ascii_digit());
+        if !is_digits(zip, 5) || plus_four.is_some_and(|plus_four| !is_digits(plus_four, 4)) {
+            return Err(invalid());
+        }
+
+        Self::new(zip.parse().map_err(|_| invalid())?)
+    }
+}
+
+impl TryFrom<u32> for ZipCode {
+    type Error = ZipCodeError;
+
+    fn try_from(zip: u32) -> Result<Self, Self::Error> {
+        Self::new(zip)
+    }
+}
+
+impl From<ZipCode> for u32 {
+    fn from(zip: ZipCode) -> Self {
+        zip.0
+    }
+}
+
+impl Display for ZipCodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ZipCodeError::InvalidFormat(input) => {
+                write!(f, "\"{input}\" is not a 5-digit or ZIP+4 zip code")
+            }
+            ZipCodeError::OutOfRange(zip) => write!(
+                f,
+                "{zip} is outside the zip code range {:05}..={:05}",
+                ZipCode::MIN,
+                ZipCode::MAX
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ZipCodeError {}
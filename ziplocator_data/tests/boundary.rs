@@ -0,0 +1,46 @@
+use ziplocator_data::{Dataset, DatasetFilter, ZipBoundaries, ZipCode};
+
+/// A GeoJSON square of 0.02 degrees around `(lat, lon)` labelled `zip`
+fn square(zip: ZipCode, (lat, lon): (f64, f64)) -> String {
+    let (south, north, west, east) = (lat - 0.01, lat + 0.01, lon - 0.01, lon + 0.01);
+    format!(
+        r#"{{"type": "Feature", "properties": {{"ZCTA5CE20": "{zip}"}}, "geometry": {{"type": "Polygon", "coordinates": [[[{west}, {south}], [{east}, {south}], [{east}, {north}], [{west}, {north}], [{west}, {south}]]]}}}}"#
+    )
+}
+
+#[test]
+fn boundaries_of_missing_zips_fall_back_to_nearest() {
+    let dataset = Dataset::synthetic(2_000, 4);
+    // The lowest and highest zips, which are in different states
+    let (kept, dropped) = (
+        dataset.records().next().unwrap(),
+        dataset.records().last().unwrap(),
+    );
+    let dropped_location = (dropped.latitude, dropped.longitude);
+
+    let path = std::env::temp_dir().join("ziplocator_boundary_test.geojson");
+    std::fs::write(
+        &path,
+        format!(
+            r#"{{"type": "FeatureCollection", "features": [{}, {}]}}"#,
+            square(kept.zip, (kept.latitude, kept.longitude)),
+            square(dropped.zip, dropped_location)
+        ),
+    )
+    .unwrap();
+    let boundaries = ZipBoundaries::from_path(&path);
+    std::fs::remove_file(path).ok();
+
+    let filtered = dataset
+        .filter(&DatasetFilter::new().exclude_states([dropped.state_id]))
+        .unwrap()
+        .with_boundaries(boundaries.unwrap());
+
+    assert_eq!(
+        filtered.zip_at(kept.latitude, kept.longitude),
+        Some(kept.zip)
+    );
+    let (lat, lon) = dropped_location;
+    assert_eq!(filtered.zip_at(lat, lon), filtered.nearest_zip(lat, lon));
+    assert_ne!(filtered.zip_at(lat, lon), Some(dropped.zip));
+}
@@ -0,0 +1,76 @@
+use ziplocator_data::{geo, Dataset, SourceFormat, ZipCode, MAX_NEIGHBOR_DISTANCE_KM};
+
+/// Zips on both sides of the antimeridian and around the Pacific
+const PACIFIC: &[(u32, f64, f64)] = &[
+    (96910, 13.4757, 144.7489),   // Hagåtña, GU
+    (96950, 15.1850, 145.7467),   // Saipan, MP
+    (96940, 7.3400, 134.4800),    // Koror, PW
+    (99546, 51.8800, -176.6581),  // Adak, AK
+    (99547, 52.1961, -174.2006),  // Atka, AK
+    (99549, 51.4500, 179.2000),   // Amchitka, AK, under a made up zip
+    (96799, -14.2781, -170.7025), // Pago Pago, AS
+    (96813, 21.3069, -157.8583),  // Honolulu, HI
+    (99501, 61.2181, -149.9003),  // Anchorage, AK
+    (99801, 58.3019, -134.4197),  // Juneau, AK
+    (98101, 47.6101, -122.3344),  // Seattle, WA
+];
+
+fn zip(zip: u32) -> ZipCode {
+    ZipCode::new(zip).unwrap()
+}
+
+fn pacific() -> Dataset {
+    let rows: String = PACIFIC
+        .iter()
+        .map(|(zip, lat, lon)| {
+            format!("US\t{zip:05}\tPlace\tState\tXX\tCounty\t\t\t\t{lat}\t{lon}\t4\n")
+        })
+        .collect();
+    let path = std::env::temp_dir().join("ziplocator_delaunay_test.txt");
+    std::fs::write(&path, rows).unwrap();
+    let dataset = SourceFormat::GEONAMES.load_path(&path).unwrap();
+    std::fs::remove_file(path).ok();
+
+    assert_eq!(dataset.len(), PACIFIC.len());
+    dataset
+}
+
+#[test]
+fn neighbours_across_the_antimeridian_but_not_the_pacific() {
+    let dataset = pacific();
+
+    assert_eq!(dataset.neighbors(zip(96910)), [zip(96950)]);
+    assert_eq!(dataset.neighbors(zip(99546)), [zip(99547), zip(99549)]);
+    assert!(dataset.neighbors(zip(96799)).is_empty());
+    assert!(dataset.neighbors(zip(96813)).is_empty());
+}
+
+#[test]
+fn neighbours_are_close_and_symmetric() {
+    let dataset = Dataset::synthetic(3_000, 6);
+
+    for (a, b) in dataset.triangulation().edges() {
+        let distance = dataset.distance_between(a, b).unwrap();
+        assert!(distance <= MAX_NEIGHBOR_DISTANCE_KM, "{a} {b} {distance}");
+        assert!(dataset.neighbors(a).contains(&b));
+        assert!(dataset.neighbors(b).contains(&a));
+    }
+}
+
+#[test]
+fn voronoi_cells_surround_their_zip() {
+    let dataset = Dataset::synthetic(3_000, 6);
+
+    let mut bounded = 0;
+    for record in dataset.records() {
+        if let Some(cell) = dataset.voronoi_cell(record.zip) {
+            assert!(geo::polygon_contains(
+                &cell,
+                record.latitude,
+                record.longitude
+            ));
+            bounded += 1;
+        }
+    }
+    assert!(bounded > dataset.len() / 2);
+}
@@ -0,0 +1,9 @@
+use ziplocator_data::geo;
+
+#[test]
+fn freely_associated_states_are_in_us() {
+    // Koror, Palikir and Majuro
+    for (lat, lon) in [(7.34, 134.48), (6.92, 158.16), (7.09, 171.38)] {
+        assert!(geo::in_us(lat, lon), "({lat}, {lon})");
+    }
+}
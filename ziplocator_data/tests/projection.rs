@@ -0,0 +1,66 @@
+use ziplocator_data::projection::{self, Projection};
+
+const PROJECTIONS: [Projection; 4] = [
+    Projection::LatLon,
+    Projection::WebMercator,
+    Projection::AlbersConus,
+    Projection::Ecef,
+];
+
+/// New York, Los Angeles, Anchorage, Honolulu, San Juan and Hagåtña
+const PLACES: [(f64, f64); 6] = [
+    (40.7484, -73.9967),
+    (34.0522, -118.2437),
+    (61.2181, -149.9003),
+    (21.3069, -157.8583),
+    (18.4655, -66.1057),
+    (13.4757, 144.7489),
+];
+
+#[test]
+fn forward_then_inverse_round_trips() {
+    for projection in PROJECTIONS {
+        for (lat, lon) in PLACES {
+            let coordinates = projection.forward(lat, lon);
+            assert_eq!(coordinates.len(), projection.dimensions());
+
+            let (back_lat, back_lon) = projection.inverse(&coordinates);
+            assert!(
+                (back_lat - lat).abs() < 1e-6 && (back_lon - lon).abs() < 1e-6,
+                "{projection}: ({lat}, {lon}) came back as ({back_lat}, {back_lon})"
+            );
+        }
+    }
+}
+
+#[test]
+fn names_round_trip() {
+    for projection in PROJECTIONS {
+        assert_eq!(
+            projection.to_string().parse::<Projection>().unwrap(),
+            projection
+        );
+    }
+}
+
+#[test]
+fn ecef_inverse_ignores_vector_length() {
+    let (lat, lon) = PLACES[0];
+    let scaled: Vec<_> = Projection::Ecef
+        .forward(lat, lon)
+        .into_iter()
+        .map(|coordinate| coordinate * 3.5)
+        .collect();
+
+    let (back_lat, back_lon) = Projection::Ecef.inverse(&scaled);
+    assert!((back_lat - lat).abs() < 1e-9 && (back_lon - lon).abs() < 1e-9);
+}
+
+#[test]
+fn web_mercator_scale_is_secant_of_latitude() {
+    for (lat, lon) in PLACES {
+        let (_, y) = projection::web_mercator(lat, lon);
+        let secant = 1.0 / lat.to_radians().cos();
+        assert!((projection::web_mercator_scale(y) - secant).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,103 @@
+use std::path::PathBuf;
+use ziplocator_data::{
+    Dataset, DatasetLoader, SourceFormat, ValidationIssue, ValidationPolicy, ZipCode,
+};
+
+/// Two rows of the 2023 national ZCTA Gazetteer file, last column padded
+/// like the real one
+const GAZETTEER: &str = "GEOID\tALAND\tAWATER\tALAND_SQMI\tAWATER_SQMI\tINTPTLAT\tINTPTLONG                                                                                                               
+00601\t166847909\t799292\t64.420\t0.309\t18.180555\t-66.749961                  
+10001\t1591219\t0\t0.614\t0.000\t40.750635\t-73.997177                  
+";
+
+/// Two rows of the GeoNames US postal code dump, without a header and with
+/// an empty accuracy column
+const GEONAMES: &str =
+    "US\t00601\tAdjuntas\tPuerto Rico\tPR\tAdjuntas Municipio\t001\t\t\t18.1788\t-66.7516\t
+US\t10001\tNew York\tNew York\tNY\tNew York County\t061\t\t\t40.7484\t-73.9967\t4
+";
+
+fn fixture(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("ziplocator_source_test_{name}"));
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+fn location(dataset: &Dataset, zip: u32) -> (f64, f64) {
+    dataset.zip_location(ZipCode::new(zip).unwrap()).unwrap()
+}
+
+#[test]
+fn gazetteer_with_padded_columns_loads() {
+    let path = fixture("gazetteer.txt", GAZETTEER);
+    let dataset = SourceFormat::CENSUS_ZCTA_GAZETTEER
+        .load_path(&path)
+        .unwrap();
+    std::fs::remove_file(path).ok();
+
+    assert_eq!(dataset.len(), 2);
+    assert_eq!(location(&dataset, 601), (18.180555, -66.749961));
+    assert_eq!(location(&dataset, 10001), (40.750635, -73.997177));
+}
+
+#[test]
+fn geonames_without_header_loads() {
+    let path = fixture("geonames.txt", GEONAMES);
+    let dataset = SourceFormat::GEONAMES.load_path(&path).unwrap();
+    std::fs::remove_file(path).ok();
+
+    assert_eq!(dataset.len(), 2);
+    assert_eq!(location(&dataset, 601), (18.1788, -66.7516));
+    let record = dataset.record(ZipCode::new(10001).unwrap()).unwrap();
+    assert_eq!(record.state_id, "NY");
+    assert_eq!(record.city, "New York");
+}
+
+#[test]
+fn formats_parse_by_name() {
+    for format in [
+        SourceFormat::SIMPLEMAPS,
+        SourceFormat::CENSUS_ZCTA_GAZETTEER,
+        SourceFormat::GEONAMES,
+    ] {
+        assert_eq!(format.to_string().parse::<SourceFormat>().unwrap(), format);
+    }
+    assert!("uszips".parse::<SourceFormat>().is_err());
+}
+
+#[test]
+fn loader_reads_local_file_in_given_format() {
+    let path = fixture("loader_geonames.txt", GEONAMES);
+    let dataset = DatasetLoader::new()
+        .path(&path)
+        .format(SourceFormat::GEONAMES)
+        .load()
+        .unwrap();
+    std::fs::remove_file(path).ok();
+
+    assert_eq!(location(&dataset, 10001), (40.7484, -73.9967));
+}
+
+#[test]
+fn local_files_are_validated() {
+    // A duplicate of 10001 and a row in the Atlantic
+    let geonames = format!(
+        "{GEONAMES}US\t10001\tNew York\tNew York\tNY\tNew York County\t061\t\t\t40.7\t-74.0\t4
+US\t10002\tNowhere\tNew York\tNY\tNew York County\t061\t\t\t35.0\t-40.0\t4
+"
+    );
+    let path = fixture("invalid_geonames.txt", &geonames);
+    let dataset = SourceFormat::GEONAMES.load_path(&path);
+    let kept = DatasetLoader::new()
+        .path(&path)
+        .format(SourceFormat::GEONAMES)
+        .validation(ValidationPolicy::Keep)
+        .load_with_report();
+    std::fs::remove_file(path).ok();
+
+    assert_eq!(dataset.unwrap().len(), 2);
+    let (kept, report) = kept.unwrap();
+    assert_eq!(kept.len(), 4);
+    assert_eq!(report.count(ValidationIssue::DuplicateZip), 1);
+    assert_eq!(report.count(ValidationIssue::OutsideUs), 1);
+}
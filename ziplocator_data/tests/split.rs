@@ -0,0 +1,88 @@
+use std::collections::{BTreeSet, HashSet};
+use ziplocator_data::{Dataset, DatasetError, DatasetFilter, SplitStrategy, ZipCode, ZipGroup};
+
+const STRATEGIES: [SplitStrategy; 6] = [
+    SplitStrategy::Random,
+    SplitStrategy::Stratified(ZipGroup::State),
+    SplitStrategy::Stratified(ZipGroup::Prefix),
+    SplitStrategy::HoldOut(ZipGroup::State),
+    SplitStrategy::HoldOut(ZipGroup::Prefix),
+    SplitStrategy::ZipRange,
+];
+
+fn zips(dataset: &Dataset) -> Vec<ZipCode> {
+    dataset.records().map(|record| record.zip).collect()
+}
+
+#[test]
+fn partitions_are_disjoint_and_cover_every_row() {
+    let dataset = Dataset::synthetic(3_000, 2);
+    let all: BTreeSet<_> = zips(&dataset).into_iter().collect();
+
+    for strategy in STRATEGIES {
+        let parts = dataset.split_with([0.8, 0.1, 0.1], 7, strategy).unwrap();
+
+        let mut seen = BTreeSet::new();
+        for part in &parts {
+            for zip in zips(part) {
+                assert!(seen.insert(zip), "{zip} in two partitions of {strategy}");
+            }
+        }
+        assert_eq!(seen, all, "{strategy}");
+    }
+}
+
+#[test]
+fn same_seed_gives_same_partitions() {
+    let dataset = Dataset::synthetic(3_000, 2);
+
+    for strategy in STRATEGIES {
+        let a = dataset.split_with([0.8, 0.1, 0.1], 7, strategy).unwrap();
+        let b = dataset.split_with([0.8, 0.1, 0.1], 7, strategy).unwrap();
+        let c = dataset.split_with([0.8, 0.1, 0.1], 8, strategy).unwrap();
+
+        assert_eq!(a.each_ref().map(zips), b.each_ref().map(zips), "{strategy}");
+        assert_ne!(a.each_ref().map(zips), c.each_ref().map(zips), "{strategy}");
+    }
+}
+
+#[test]
+fn held_out_groups_stay_in_one_partition() {
+    let dataset = Dataset::synthetic(3_000, 2);
+
+    for group in [ZipGroup::State, ZipGroup::Prefix] {
+        let parts = dataset
+            .split_with([0.8, 0.1, 0.1], 7, SplitStrategy::HoldOut(group))
+            .unwrap();
+
+        let mut seen = HashSet::new();
+        for part in &parts {
+            let keys: HashSet<_> = part
+                .records()
+                .map(|record| match group {
+                    ZipGroup::State => record.state_id,
+                    ZipGroup::Prefix => format!("{:03}", record.zip.get() / 100),
+                })
+                .collect();
+            for key in keys {
+                assert!(seen.insert(key.clone()), "{key} in two partitions");
+            }
+        }
+    }
+}
+
+#[test]
+fn empty_partitions_are_rejected() {
+    let california = Dataset::synthetic(2_000, 1)
+        .filter(&DatasetFilter::new().states(["CA"]))
+        .unwrap();
+
+    let held_out =
+        california.split_with([0.8, 0.1, 0.1], 42, SplitStrategy::HoldOut(ZipGroup::State));
+    assert!(matches!(held_out, Err(DatasetError::InvalidSplit(_))));
+
+    let [train, valid] = california
+        .split_with([1.0, 0.0], 42, SplitStrategy::HoldOut(ZipGroup::State))
+        .unwrap();
+    assert_eq!((train.len(), valid.len()), (california.len(), 0));
+}
@@ -0,0 +1,35 @@
+use ziplocator_data::{Dataset, ValidationPolicy};
+
+#[test]
+fn same_seed_gives_same_dataset() {
+    let a = Dataset::synthetic(500, 7);
+    let b = Dataset::synthetic(500, 7);
+    let c = Dataset::synthetic(500, 8);
+
+    assert_eq!(a.len(), 500);
+    assert!(a.dataframe().equals_missing(b.dataframe()));
+    assert!(!a.dataframe().equals_missing(c.dataframe()));
+}
+
+#[test]
+fn synthetic_rows_pass_validation() {
+    let (dataset, report) = Dataset::synthetic(2_000, 1)
+        .validate(ValidationPolicy::Error)
+        .unwrap();
+
+    assert_eq!(dataset.len(), 2_000);
+    assert!(report.invalid.is_empty());
+}
+
+#[test]
+fn oversized_request_is_capped() {
+    for seed in 0..3 {
+        let dataset = Dataset::synthetic(usize::MAX, seed);
+        let rows = dataset.len();
+
+        assert!(rows > 50_000);
+        let (_, report) = dataset.validate(ValidationPolicy::Error).unwrap();
+        assert!(report.invalid.is_empty());
+        assert_eq!(Dataset::synthetic(rows + 1, seed).len(), rows);
+    }
+}
@@ -0,0 +1,30 @@
+use ziplocator_data::{ZipCode, ZipCodeError};
+
+#[test]
+fn parses_five_digit_and_zip_plus_four() {
+    let zip: ZipCode = "02134".parse().unwrap();
+    assert_eq!(zip.get(), 2134);
+    assert_eq!(zip.to_string(), "02134");
+
+    assert_eq!("02134-1234".parse::<ZipCode>(), Ok(zip));
+    assert_eq!(" 02134 ".parse::<ZipCode>(), Ok(zip));
+}
+
+#[test]
+fn rejects_malformed_and_out_of_range_zips() {
+    for input in ["9999999", "2134", "02134-12", "0213a", "02134-"] {
+        assert!(
+            matches!(
+                input.parse::<ZipCode>(),
+                Err(ZipCodeError::InvalidFormat(_))
+            ),
+            "{input}"
+        );
+    }
+
+    assert_eq!("00000".parse::<ZipCode>(), Err(ZipCodeError::OutOfRange(0)));
+    assert_eq!(
+        ZipCode::new(100_000),
+        Err(ZipCodeError::OutOfRange(100_000))
+    );
+}
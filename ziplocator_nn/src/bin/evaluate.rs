@@ -0,0 +1,41 @@
+use burn::backend::{ndarray::NdArrayDevice, NdArray};
+use ziplocator_nn::TrainingConfig;
+
+fn main() {
+    let artifact_dir = ziplocator_nn::artifact_dir_from_args(std::env::args().skip(1))
+        .unwrap_or_else(|error| {
+            eprintln!("{error}\n\n{}", ziplocator_nn::MODEL_USAGE);
+            std::process::exit(2);
+        });
+    let training = TrainingConfig::from_artifact_dir(&artifact_dir).unwrap_or_else(|error| {
+        eprintln!("Loading the training config from {artifact_dir} failed: {error}");
+        std::process::exit(1);
+    });
+
+    let (dataset, report) = training.load_dataset_with_report().unwrap_or_else(|error| {
+        eprintln!("Loading dataset failed: {error}");
+        std::process::exit(1);
+    });
+    eprintln!("Dataset validation: {report}");
+
+    match ziplocator_nn::evaluate::<NdArray>(NdArrayDevice::Cpu, &dataset, &training) {
+        Ok(evaluation) => {
+            println!(
+                "{} test zips ({} split): mean error {:.1} km, median {:.1} km, max {:.1} km",
+                evaluation.zips,
+                training.split,
+                evaluation.mean_error_km,
+                evaluation.median_error_km,
+                evaluation.max_error_km
+            );
+            println!(
+                "Population weighted: mean error {:.1} km, median {:.1} km",
+                evaluation.population_mean_error_km, evaluation.population_median_error_km
+            );
+        }
+        Err(error) => {
+            eprintln!("Evaluation failed: {error}");
+            std::process::exit(1);
+        }
+    }
+}
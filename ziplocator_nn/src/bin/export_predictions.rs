@@ -0,0 +1,60 @@
+use burn::backend::{ndarray::NdArrayDevice, NdArray};
+use ziplocator_data::{ExportFormat, Prediction};
+use ziplocator_nn::{Inferrer, TrainingConfig};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let format: ExportFormat = match args.next().map(|format| format.parse()) {
+        Some(Ok(format)) => format,
+        Some(Err(error)) => {
+            eprintln!("{error}");
+            std::process::exit(2);
+        }
+        None => {
+            eprintln!(
+                "Usage: export_predictions geojson|kml|csv [options] > <file>\n\n{}",
+                ziplocator_nn::MODEL_USAGE
+            );
+            std::process::exit(2);
+        }
+    };
+    let artifact_dir = ziplocator_nn::artifact_dir_from_args(args).unwrap_or_else(|error| {
+        eprintln!("{error}\n\n{}", ziplocator_nn::MODEL_USAGE);
+        std::process::exit(2);
+    });
+    let training = TrainingConfig::from_artifact_dir(&artifact_dir).unwrap_or_else(|error| {
+        eprintln!("Loading the training config from {artifact_dir} failed: {error}");
+        std::process::exit(1);
+    });
+
+    let (dataset, report) = training.load_dataset_with_report().unwrap_or_else(|error| {
+        eprintln!("Loading dataset failed: {error}");
+        std::process::exit(1);
+    });
+    eprintln!("Dataset validation: {report}");
+
+    let inferrer = ziplocator_nn::InferrerImpl::<NdArray>::load_from(
+        NdArrayDevice::Cpu,
+        &training.artifact_dir,
+    );
+    let predictions: Vec<_> = dataset
+        .records()
+        .map(|record| {
+            let predicted = inferrer.infer(record.zip, None);
+            Prediction {
+                zip: record.zip,
+                latitude: predicted.latitude,
+                longitude: predicted.longitude,
+            }
+        })
+        .collect();
+
+    if let Err(error) = dataset
+        .exporter()
+        .predictions(predictions)
+        .write(format, std::io::stdout().lock())
+    {
+        eprintln!("Exporting predictions failed: {error}");
+        std::process::exit(1);
+    }
+}
@@ -1,12 +1,13 @@
 use std::io::BufRead;
 
 use burn::backend::{ndarray::NdArrayDevice, NdArray};
+use ziplocator_data::ZipCode;
 use ziplocator_nn::Inferrer;
 
 fn main() {
     let inferrer = ziplocator_nn::InferrerImpl::<NdArray>::load(NdArrayDevice::Cpu);
     println!("Enter zip code:");
-    let zip: u32 = std::io::stdin()
+    let zip: ZipCode = std::io::stdin()
         .lock()
         .lines()
         .next()
@@ -1,5 +1,22 @@
 use burn::backend::{ndarray::NdArrayDevice, Autodiff, NdArray};
+use ziplocator_nn::TrainingConfig;
 
 fn main() {
-    ziplocator_nn::train::<Autodiff<NdArray>>(&NdArrayDevice::Cpu);
+    let training = TrainingConfig::from_args(std::env::args().skip(1)).unwrap_or_else(|error| {
+        eprintln!("{error}\n\n{}", ziplocator_nn::USAGE);
+        std::process::exit(2);
+    });
+
+    let (dataset, report) = training.load_dataset_with_report().unwrap_or_else(|error| {
+        eprintln!("Loading dataset failed: {error}");
+        std::process::exit(1);
+    });
+    eprintln!("Dataset validation: {report}");
+
+    if let Err(error) =
+        ziplocator_nn::train::<Autodiff<NdArray>>(&NdArrayDevice::Cpu, &dataset, &training)
+    {
+        eprintln!("Training failed: {error}");
+        std::process::exit(1);
+    }
 }
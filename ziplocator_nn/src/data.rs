@@ -1,6 +1,7 @@
 use std::{
-    sync::Arc,
-    time::{SystemTime, UNIX_EPOCH},
+    fmt::Display,
+    str::FromStr,
+    sync::{Arc, Mutex},
 };
 
 use burn::{
@@ -9,19 +10,31 @@ use burn::{
     tensor::{Tensor, TensorData},
 };
 use burn_dataset::DataframeDataset;
-use serde::Deserialize;
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    rngs::StdRng,
+    SeedableRng,
+};
+use serde::{Deserialize, Serialize};
+use ziplocator_data::{projection::Projection, Dataset, ZipCode};
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct ZipItem {
-    pub zip: u32,
+    pub zip: ZipCode,
     #[serde(rename = "lat")]
     pub latitude: f64,
     #[serde(rename = "lng")]
     pub longitude: f64,
 }
 
+/// Batches zips as bit tensors and their locations in the `projection`'s
+/// target space.
 #[derive(Clone, Debug)]
-pub struct ZipBatcher<B: Backend>(pub B::Device);
+pub struct ZipBatcher<B: Backend> {
+    pub device: B::Device,
+    pub input_size: usize,
+    pub projection: Projection,
+}
 
 #[derive(Clone, Debug)]
 pub struct ZipBatch<B: Backend> {
@@ -29,32 +42,139 @@ pub struct ZipBatch<B: Backend> {
     pub locations: Tensor<B, 2>,
 }
 
-pub fn create_loader<B: Backend>(device: &B::Device) -> Arc<dyn DataLoader<ZipBatch<B>>> {
-    let dataframe = ziplocator_data::Dataset::load().dataframe();
-    let dataset = DataframeDataset::new(dataframe).expect("Create dataset from dataframe");
-
-    DataLoaderBuilder::new(ZipBatcher(device.clone()))
-        .batch_size(100)
-        .shuffle(
-            SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_millis() as u64,
-        )
-        .build(dataset)
+/// How likely each zip is to be drawn into a training batch
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Sampling {
+    /// Every zip once per epoch
+    #[default]
+    Uniform,
+    /// Drawn with replacement in proportion to the zip's population
+    Population,
+    /// Like [`Sampling::Population`], but dampened so rural zips are not
+    /// drowned out by the largest cities
+    SqrtPopulation,
 }
 
-pub fn create_zip_tensor<B: Backend>(device: &B::Device, zip: u32) -> Tensor<B, 2> {
-    let digits: Vec<f64> = format!("{:b}", zip)
-        .chars()
-        .map(|d| d.to_digit(2).unwrap() as f64)
-        .collect();
-    let digits = std::iter::repeat(0.0)
-        .take(crate::INPUT_SIZE - digits.len())
-        .chain(digits)
+impl Sampling {
+    /// Zips without a population figure get no weight
+    pub fn weight(self, population: Option<u64>) -> f64 {
+        let population = population.unwrap_or(0) as f64;
+        match self {
+            Sampling::Uniform => 1.0,
+            Sampling::Population => population,
+            Sampling::SqrtPopulation => population.sqrt(),
+        }
+    }
+}
+
+impl Display for Sampling {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Sampling::Uniform => "uniform",
+            Sampling::Population => "population",
+            Sampling::SqrtPopulation => "sqrt-population",
+        })
+    }
+}
+
+impl FromStr for Sampling {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "uniform" => Sampling::Uniform,
+            "population" => Sampling::Population,
+            "sqrt-population" => Sampling::SqrtPopulation,
+            _ => {
+                return Err(format!(
+                    "Unknown sampling \"{s}\", expected uniform, population or sqrt-population"
+                ))
+            }
+        })
+    }
+}
+
+/// Draws as many items as it holds, with replacement, each in proportion to
+/// its weight.
+struct WeightedDataset {
+    items: Vec<ZipItem>,
+    weights: WeightedIndex<f64>,
+    rng: Mutex<StdRng>,
+}
+
+impl burn_dataset::Dataset<ZipItem> for WeightedDataset {
+    fn get(&self, index: usize) -> Option<ZipItem> {
+        if index >= self.items.len() {
+            return None;
+        }
+
+        let index = self.weights.sample(&mut *self.rng.lock().unwrap());
+        self.items.get(index).cloned()
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+
+/// Batches, shuffles and samples as set in `training`. Falls back to uniform
+/// sampling when no zip has a population, e.g. for sources without a
+/// population column.
+pub fn create_loader<B: Backend>(
+    device: &B::Device,
+    dataset: &Dataset,
+    input_size: usize,
+    training: &crate::TrainingConfig,
+) -> Result<Arc<dyn DataLoader<ZipBatch<B>>>, crate::TrainingError> {
+    let (sampling, seed) = (training.sampling, training.seed);
+    let builder = DataLoaderBuilder::new(ZipBatcher {
+        device: device.clone(),
+        input_size,
+        projection: training.projection,
+    })
+    .batch_size(training.batch_size)
+    .shuffle(seed);
+
+    if sampling != Sampling::Uniform {
+        let (items, weights): (Vec<_>, Vec<_>) = dataset
+            .records()
+            .map(|record| {
+                let item = ZipItem {
+                    zip: record.zip,
+                    latitude: record.latitude,
+                    longitude: record.longitude,
+                };
+                (item, sampling.weight(record.population))
+            })
+            .unzip();
+
+        if let Ok(weights) = WeightedIndex::new(weights) {
+            return Ok(builder.build(WeightedDataset {
+                items,
+                weights,
+                rng: Mutex::new(StdRng::seed_from_u64(seed)),
+            }));
+        }
+    }
+
+    let dataset = DataframeDataset::new(dataset.dataframe().clone())?;
+    Ok(builder.build(dataset))
+}
+
+/// The zip's binary digits, most significant first, zero padded to
+/// `input_size` bits.
+pub fn create_zip_tensor<B: Backend>(
+    device: &B::Device,
+    zip: ZipCode,
+    input_size: usize,
+) -> Tensor<B, 2> {
+    let digits: Vec<f64> = (0..input_size)
+        .rev()
+        .map(|bit| (zip.get().checked_shr(bit as u32).unwrap_or(0) & 1) as f64)
         .collect();
 
-    let zip_data = TensorData::new::<f64, _>(digits, vec![1, crate::INPUT_SIZE]);
+    let zip_data = TensorData::new::<f64, _>(digits, vec![1, input_size]);
 
     Tensor::from_data(zip_data, device)
 }
@@ -64,18 +184,19 @@ impl<B: Backend> Batcher<ZipItem, ZipBatch<B>> for ZipBatcher<B> {
         let (zips, locations): (Vec<Tensor<B, 2>>, Vec<Tensor<B, 2>>) = items
             .into_iter()
             .map(|item| {
+                let location = self.projection.forward(item.latitude, item.longitude);
                 let location_data =
-                    TensorData::new::<f64, _>(vec![item.latitude, item.longitude], vec![1, 2]);
+                    TensorData::new::<f64, _>(location, vec![1, self.projection.dimensions()]);
                 (
-                    create_zip_tensor(&self.0, item.zip),
-                    Tensor::from_data(location_data, &self.0),
+                    create_zip_tensor(&self.device, item.zip, self.input_size),
+                    Tensor::from_data(location_data, &self.device),
                 )
             })
             .unzip();
 
         ZipBatch {
-            zips: Tensor::cat(zips, 0).to_device(&self.0),
-            locations: Tensor::cat(locations, 0).to_device(&self.0),
+            zips: Tensor::cat(zips, 0).to_device(&self.device),
+            locations: Tensor::cat(locations, 0).to_device(&self.device),
         }
     }
 }
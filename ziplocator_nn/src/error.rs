@@ -0,0 +1,62 @@
+use burn_dataset::DataframeDatasetError;
+use std::{fmt::Display, path::PathBuf};
+use ziplocator_data::DatasetError;
+
+#[derive(Debug)]
+pub enum TrainingError {
+    Dataset(DatasetError),
+    InvalidConfig(String),
+    Loader(DataframeDatasetError),
+    Save {
+        file: PathBuf,
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+impl TrainingError {
+    pub(crate) fn saving<E>(file: PathBuf) -> impl FnOnce(E) -> Self
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        move |error| TrainingError::Save {
+            file,
+            error: Box::new(error),
+        }
+    }
+}
+
+impl Display for TrainingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrainingError::Dataset(error) => write!(f, "{error}"),
+            TrainingError::InvalidConfig(reason) => write!(f, "Invalid training config: {reason}"),
+            TrainingError::Loader(error) => write!(f, "Creating the data loader failed: {error}"),
+            TrainingError::Save { file, error } => {
+                write!(f, "Saving {} failed: {error}", file.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for TrainingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TrainingError::Dataset(error) => Some(error),
+            TrainingError::Loader(error) => Some(error),
+            TrainingError::Save { error, .. } => Some(error.as_ref()),
+            TrainingError::InvalidConfig(_) => None,
+        }
+    }
+}
+
+impl From<DatasetError> for TrainingError {
+    fn from(error: DatasetError) -> Self {
+        TrainingError::Dataset(error)
+    }
+}
+
+impl From<DataframeDatasetError> for TrainingError {
+    fn from(error: DataframeDatasetError) -> Self {
+        TrainingError::Loader(error)
+    }
+}
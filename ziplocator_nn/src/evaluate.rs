@@ -0,0 +1,67 @@
+use burn::prelude::Backend;
+use ziplocator_data::{geo, Dataset, DatasetError};
+
+use crate::Inferrer;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Evaluation {
+    pub zips: usize,
+    pub mean_error_km: f64,
+    pub median_error_km: f64,
+    pub max_error_km: f64,
+    /// Errors weighted by each zip's population, i.e. as seen by a typical
+    /// resident rather than a typical zip. NaN without population figures.
+    pub population_mean_error_km: f64,
+    pub population_median_error_km: f64,
+}
+
+/// Evaluates the model saved in `training.artifact_dir` on the test partition
+/// of `dataset`. Use the config saved with the model, see
+/// [`crate::TrainingConfig::from_artifact_dir`], so the test zips are the ones
+/// held out during training.
+pub fn evaluate<B: Backend>(
+    device: B::Device,
+    dataset: &Dataset,
+    training: &crate::TrainingConfig,
+) -> Result<Evaluation, DatasetError> {
+    let [_train, _valid, test] = training.split_dataset(dataset)?;
+    let inferrer = crate::InferrerImpl::<B>::load_from(device, &training.artifact_dir);
+
+    let mut weighted_errors: Vec<(f64, f64)> = test
+        .records()
+        .map(|record| {
+            let predicted = inferrer.infer(record.zip, None);
+            let error = geo::haversine_distance(
+                (record.latitude, record.longitude),
+                (predicted.latitude, predicted.longitude),
+            );
+            (error, crate::Sampling::Population.weight(record.population))
+        })
+        .collect();
+    weighted_errors.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+    let errors: Vec<f64> = weighted_errors.iter().map(|&(error, _)| error).collect();
+    let population: f64 = weighted_errors.iter().map(|&(_, weight)| weight).sum();
+    let population_median_error_km = weighted_errors
+        .iter()
+        .scan(0.0, |cumulative, &(error, weight)| {
+            *cumulative += weight;
+            Some((*cumulative, error))
+        })
+        .find(|&(cumulative, _)| cumulative >= population / 2.0)
+        .filter(|_| population > 0.0)
+        .map_or(f64::NAN, |(_, error)| error);
+
+    Ok(Evaluation {
+        zips: errors.len(),
+        mean_error_km: errors.iter().sum::<f64>() / errors.len() as f64,
+        median_error_km: errors.get(errors.len() / 2).copied().unwrap_or(f64::NAN),
+        max_error_km: errors.last().copied().unwrap_or(f64::NAN),
+        population_mean_error_km: weighted_errors
+            .iter()
+            .map(|&(error, weight)| error * weight)
+            .sum::<f64>()
+            / population,
+        population_median_error_km,
+    })
+}
@@ -1,12 +1,19 @@
 use burn::{
     backend::{ndarray::NdArrayDevice, NdArray},
+    config::Config,
     module::Module,
     prelude::Backend,
     record::{FullPrecisionSettings, PrettyJsonFileRecorder},
 };
+use std::path::Path;
+use ziplocator_data::ZipCode;
 
 pub trait Inferrer {
-    fn infer(&self, zip: u32, recorder: Option<&mut crate::LayerOutputRecorder>) -> crate::ZipItem;
+    fn infer(
+        &self,
+        zip: ZipCode,
+        recorder: Option<&mut crate::LayerOutputRecorder>,
+    ) -> crate::ZipItem;
 }
 
 pub struct InferrerImpl<B: Backend> {
@@ -16,9 +23,18 @@ pub struct InferrerImpl<B: Backend> {
 
 impl<B: Backend> InferrerImpl<B> {
     pub fn load(device: B::Device) -> Self {
-        let model = crate::ZipModel::<B>::new(&device)
+        Self::load_from(device, crate::ARTIFACT_DIR)
+    }
+
+    pub fn load_from(device: B::Device, artifact_dir: impl AsRef<Path>) -> Self {
+        let artifact_dir = artifact_dir.as_ref();
+        let config = crate::ZipModelConfig::load(artifact_dir.join(crate::MODEL_CONFIG_FILE))
+            .expect("Unable to load model config from file");
+
+        let model = config
+            .init::<B>(&device)
             .load_file(
-                format!("{}{}", crate::ARTIFACT_DIR, crate::MODEL_FILE),
+                artifact_dir.join(crate::MODEL_FILE),
                 &PrettyJsonFileRecorder::<FullPrecisionSettings>::new(),
                 &device,
             )
@@ -29,16 +45,21 @@ impl<B: Backend> InferrerImpl<B> {
 }
 
 impl<B: Backend> Inferrer for InferrerImpl<B> {
-    fn infer(&self, zip: u32, recorder: Option<&mut crate::LayerOutputRecorder>) -> crate::ZipItem {
-        let zips = crate::create_zip_tensor(&self.device, zip);
+    fn infer(
+        &self,
+        zip: ZipCode,
+        recorder: Option<&mut crate::LayerOutputRecorder>,
+    ) -> crate::ZipItem {
+        let zips = crate::create_zip_tensor(&self.device, zip, self.model.input_size());
 
         let locations = self.model.forward(zips, recorder);
-        let locations_data = locations.into_data().to_vec::<f32>().unwrap();
+        let locations_data = locations.into_data().convert::<f64>().to_vec().unwrap();
+        let (latitude, longitude) = self.model.projection().inverse(&locations_data);
 
         crate::ZipItem {
             zip,
-            latitude: locations_data[0] as f64,
-            longitude: locations_data[1] as f64,
+            latitude,
+            longitude,
         }
     }
 }
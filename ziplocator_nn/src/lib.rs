@@ -1,12 +1,24 @@
 pub mod data;
+mod error;
+mod evaluate;
 mod infer;
 pub mod model;
+mod options;
 mod train;
 
 pub use data::*;
+pub use error::*;
+pub use evaluate::*;
 pub use infer::*;
 pub use model::*;
+pub use options::*;
 pub use train::*;
 
 pub const ARTIFACT_DIR: &str = "./learn/";
 pub const MODEL_FILE: &str = "model.json";
+pub const MODEL_CONFIG_FILE: &str = "model_config.json";
+pub const TRAINING_CONFIG_FILE: &str = "training_config.json";
+
+/// Train, validation and test shares of the dataset
+pub const SPLIT_RATIOS: [f64; 3] = [0.8, 0.1, 0.1];
+pub const SPLIT_SEED: u64 = 42;
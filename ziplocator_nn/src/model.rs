@@ -1,12 +1,96 @@
 use burn::{
-    module::Module,
-    nn::{loss::MseLoss, Linear, LinearConfig, Relu, Tanh},
+    config::Config,
+    module::{Ignored, Module},
+    nn::{Linear, LinearConfig, Relu, Tanh},
     prelude::Backend,
     tensor::Tensor,
     train::RegressionOutput,
 };
+use ziplocator_data::{geo, projection::Projection, Dataset, DatasetError, ZipCode};
 
-pub const INPUT_SIZE: usize = 18;
+type RecordFn<'a, B> = Box<dyn FnMut(&Tensor<B, 2>) + 'a>;
+
+/// Input width, target space and output normalization of [`ZipModel`]. The
+/// normalization is taken from the data it is trained on, while the input
+/// always fits every zip code, so zips outside a filtered training set are
+/// not truncated at inference.
+#[derive(Config, Debug)]
+pub struct ZipModelConfig {
+    pub input_size: usize,
+    #[config(default = "Projection::LatLon")]
+    pub projection: Projection,
+    /// Per-coordinate mean and standard deviation of the projected targets
+    pub output_mean: Vec<f64>,
+    pub output_std: Vec<f64>,
+}
+
+impl ZipModelConfig {
+    /// Plain coordinates are normalized with the [`DatasetSummary`]
+    /// statistics, projected ones with the same statistics computed in the
+    /// target space. Pass the training partition, not the whole dataset.
+    ///
+    /// [`DatasetSummary`]: ziplocator_data::DatasetSummary
+    pub fn from_dataset(dataset: &Dataset, projection: Projection) -> Result<Self, DatasetError> {
+        let (output_mean, output_std): (Vec<_>, Vec<_>) = match projection {
+            Projection::LatLon => {
+                let summary = dataset.summary()?;
+                vec![
+                    (summary.lat_mean, summary.lat_std),
+                    (summary.lng_mean, summary.lng_std),
+                ]
+                .into_iter()
+                .unzip()
+            }
+            projection => projected_statistics(dataset, projection)
+                .into_iter()
+                .unzip(),
+        };
+
+        // A constant coordinate would otherwise scale every output to zero
+        let output_std = output_std
+            .into_iter()
+            .map(|std| if std > 0.0 { std } else { 1.0 })
+            .collect();
+
+        Ok(Self::new(ZipCode::BITS as usize, output_mean, output_std).with_projection(projection))
+    }
+
+    pub fn init<B: Backend>(&self, device: &B::Device) -> ZipModel<B> {
+        ZipModel {
+            lin1: LinearConfig::new(self.input_size, 64).init(device),
+            lin2: LinearConfig::new(64, 32).init(device),
+            lin3: LinearConfig::new(32, 16).init(device),
+            lin4: LinearConfig::new(16, 8).init(device),
+            lin5: LinearConfig::new(8, self.projection.dimensions()).init(device),
+            input_size: self.input_size,
+            projection: Ignored(self.projection),
+            output_mean: self.output_mean.clone(),
+            output_std: self.output_std.clone(),
+        }
+    }
+}
+
+/// Mean and sample standard deviation of each projected coordinate, matching
+/// how [`ziplocator_data::DatasetSummary`] computes them
+fn projected_statistics(dataset: &Dataset, projection: Projection) -> Vec<(f64, f64)> {
+    let targets: Vec<_> = dataset
+        .records()
+        .map(|record| projection.forward(record.latitude, record.longitude))
+        .collect();
+
+    let count = targets.len() as f64;
+    (0..projection.dimensions())
+        .map(|i| {
+            let mean = targets.iter().map(|target| target[i]).sum::<f64>() / count;
+            let variance = targets
+                .iter()
+                .map(|target| (target[i] - mean).powi(2))
+                .sum::<f64>()
+                / (count - 1.0);
+            (mean, variance.sqrt())
+        })
+        .collect()
+}
 
 #[derive(Module, Debug)]
 pub struct ZipModel<B: Backend> {
@@ -15,17 +99,19 @@ pub struct ZipModel<B: Backend> {
     lin3: Linear<B>,
     lin4: Linear<B>,
     lin5: Linear<B>,
+    input_size: usize,
+    projection: Ignored<Projection>,
+    output_mean: Vec<f64>,
+    output_std: Vec<f64>,
 }
 
 impl<B: Backend> ZipModel<B> {
-    pub fn new(device: &B::Device) -> Self {
-        Self {
-            lin1: LinearConfig::new(INPUT_SIZE, 64).init(device),
-            lin2: LinearConfig::new(64, 32).init(device),
-            lin3: LinearConfig::new(32, 16).init(device),
-            lin4: LinearConfig::new(16, 8).init(device),
-            lin5: LinearConfig::new(8, 2).init(device),
-        }
+    pub fn input_size(&self) -> usize {
+        self.input_size
+    }
+
+    pub fn projection(&self) -> Projection {
+        self.projection.0
     }
 
     pub fn forward(
@@ -33,7 +119,7 @@ impl<B: Backend> ZipModel<B> {
         mut x: Tensor<B, 2>,
         recorder: Option<&mut LayerOutputRecorder>,
     ) -> Tensor<B, 2> {
-        let mut record: Box<dyn FnMut(&Tensor<B, 2>)> = if let Some(recorder) = recorder {
+        let mut record: RecordFn<B> = if let Some(recorder) = recorder {
             Box::new(|tensor| {
                 recorder
                     .layers
@@ -64,7 +150,10 @@ impl<B: Backend> ZipModel<B> {
         x = self.lin5.forward(x);
         record(&x);
 
-        x = x * 100.0;
+        let device = x.device();
+        let std = Tensor::<B, 1>::from_floats(self.output_std.as_slice(), &device);
+        let mean = Tensor::<B, 1>::from_floats(self.output_mean.as_slice(), &device);
+        x = x * std.unsqueeze() + mean.unsqueeze();
         record(&x);
 
         x
@@ -72,15 +161,55 @@ impl<B: Backend> ZipModel<B> {
 
     pub fn forward_regression(&self, batch: crate::ZipBatch<B>) -> RegressionOutput<B> {
         let outputs = self.forward(batch.zips.clone(), None);
-        let loss = MseLoss::new()
-            .forward_no_reduction(outputs.clone(), batch.locations.clone())
-            .sum_dim(1)
-            .sqrt()
-            .squeeze(1);
+        let projected_distance = || {
+            (outputs.clone() - batch.locations.clone())
+                .powi_scalar(2)
+                .sum_dim(1)
+                .sqrt()
+        };
+        let loss = match self.projection() {
+            Projection::LatLon => haversine_distance(outputs.clone(), batch.locations.clone()),
+            Projection::WebMercator => {
+                // Undo the sec(lat) stretch at the target, cosh written out
+                // as tensors have no cosh
+                let y = batch
+                    .locations
+                    .clone()
+                    .slice([None, Some((1, 2))])
+                    .div_scalar(geo::WGS84_SEMI_MAJOR_AXIS_KM);
+                let scale = (y.clone().exp() + y.neg().exp()).div_scalar(2.0);
+                (projected_distance() / scale).squeeze(1)
+            }
+            projection => projected_distance()
+                .mul_scalar(projection.unit_km().unwrap_or(1.0))
+                .squeeze(1),
+        };
         RegressionOutput::new(loss, outputs, batch.locations)
     }
 }
 
+/// Great-circle distance in kilometres between rows of `(lat, lon)` degrees.
+/// Tensors have no `asin`, so it is expanded as a series, which stays within
+/// 0.1% for distances up to about 6000 km.
+fn haversine_distance<B: Backend>(from: Tensor<B, 2>, to: Tensor<B, 2>) -> Tensor<B, 1> {
+    let from = from.mul_scalar(std::f64::consts::PI / 180.0);
+    let to = to.mul_scalar(std::f64::consts::PI / 180.0);
+    let lat_from = from.clone().slice([None, Some((0, 1))]);
+    let lat_to = to.clone().slice([None, Some((0, 1))]);
+
+    let half_delta = (to - from).div_scalar(2.0).sin().powi_scalar(2);
+    let half_delta_lat = half_delta.clone().slice([None, Some((0, 1))]);
+    let half_delta_lon = half_delta.slice([None, Some((1, 2))]);
+
+    let a = half_delta_lat + lat_from.cos() * lat_to.cos() * half_delta_lon;
+    let h = a.sqrt();
+    let h3 = h.clone().powi_scalar(3);
+    let h5 = h3.clone() * h.clone().powi_scalar(2);
+    let asin = h + h3.div_scalar(6.0) + h5.mul_scalar(3.0 / 40.0);
+
+    asin.mul_scalar(2.0 * geo::EARTH_RADIUS_KM).squeeze(1)
+}
+
 #[derive(Default)]
 pub struct LayerOutputRecorder {
     pub layers: Vec<Vec<f64>>,
@@ -0,0 +1,145 @@
+use std::{fmt::Display, str::FromStr};
+
+use ziplocator_data::{
+    Dataset, DatasetError, DatasetLoader, DatasetSource, ValidationReport, CONTIGUOUS_STATES,
+    DATASET_ENV, DATASET_FORMAT_ENV, DATASET_PATH_ENV,
+};
+
+/// Options of the training binary
+pub const USAGE: &str = "Options, later ones override earlier ones:
+  --config <file>        training config as JSON or TOML, e.g. the
+                         training_config.json saved with a model; fields
+                         missing from it keep their defaults
+  --artifact-dir <dir>   where the model is saved (./learn/)
+
+Dataset:
+  --dataset <name>       source to download ($ZIPLOCATOR_DATASET, simplemaps-1.90)
+  --dataset-path <file>  local copy to read instead ($ZIPLOCATOR_DATASET_PATH)
+  --dataset-format <f>   simplemaps, census or geonames, reads --dataset-path
+                         unverified ($ZIPLOCATOR_DATASET_FORMAT)
+  --split <strategy>     random, stratified-state (default), stratified-prefix,
+                         holdout-state, holdout-prefix or zip-range
+  --contiguous-48        only use the contiguous 48 states and DC
+  --exclude-territories  drop Puerto Rico, Guam and the other territories
+  --states <ids>         comma separated state ids, e.g. CA,NV,OR
+  --invalid-rows <what>  drop (default), keep or error on rows failing validation
+
+Training:
+  --epochs <n>           (1000)
+  --batch-size <n>       (100)
+  --learning-rate <lr>   initial learning rate (0.01)
+  --lr-decay <gamma>     learning rate factor per step (0.9999)
+  --seed <n>             backend and data loader seed (42)
+  --projection <space>   training target space: latlon (default), web-mercator,
+                         albers-conus or ecef
+  --sampling <weights>   how training zips are drawn: uniform (default),
+                         population or sqrt-population";
+
+/// Options of the binaries that use a trained model, which take the dataset
+/// source, filter and split from the config saved with it
+pub const MODEL_USAGE: &str = "Options:
+  --artifact-dir <dir>   where the model and its training config were saved
+                         (./learn/)";
+
+impl crate::TrainingConfig {
+    /// Parses the command line options listed in [`USAGE`].
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut training = Self::new();
+        if let Ok(name) = std::env::var(DATASET_ENV) {
+            training.dataset = name;
+        }
+        training.dataset_path = std::env::var(DATASET_PATH_ENV).ok();
+        training.dataset_format = std::env::var(DATASET_FORMAT_ENV).ok();
+
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("Missing value for {arg}"));
+            match arg.as_str() {
+                "--dataset" => training.dataset = value()?,
+                "--dataset-path" => training.dataset_path = Some(value()?),
+                "--dataset-format" => training.dataset_format = Some(value()?),
+                "--split" => training.split = value()?.parse().map_err(|e| format!("{e}"))?,
+                "--contiguous-48" => {
+                    training.filter = training.filter.states(CONTIGUOUS_STATES);
+                }
+                "--exclude-territories" => {
+                    training.filter = training.filter.territories(false);
+                }
+                "--invalid-rows" => training.validation = parse(&arg, value()?)?,
+                "--states" => training.filter = training.filter.states(value()?.split(',')),
+                "--artifact-dir" => training.artifact_dir = value()?,
+                "--config" => {
+                    training = Self::load_file(value()?)
+                        .map_err(|e| format!("Loading {arg} failed: {e}"))?;
+                }
+                "--epochs" => training.num_epochs = parse(&arg, value()?)?,
+                "--batch-size" => training.batch_size = parse(&arg, value()?)?,
+                "--learning-rate" => training.learning_rate = parse(&arg, value()?)?,
+                "--lr-decay" => training.learning_rate_decay = parse(&arg, value()?)?,
+                "--seed" => training.seed = parse(&arg, value()?)?,
+                "--projection" => training.projection = parse(&arg, value()?)?,
+                "--sampling" => training.sampling = parse(&arg, value()?)?,
+                _ => return Err(format!("Unknown option {arg}")),
+            }
+        }
+
+        Ok(training)
+    }
+
+    /// Loads, validates and filters the dataset.
+    pub fn load_dataset(&self) -> Result<Dataset, DatasetError> {
+        self.load_dataset_with_report().map(|(dataset, _)| dataset)
+    }
+
+    /// Like [`Self::load_dataset`], also returning what validation found
+    /// before filtering.
+    pub fn load_dataset_with_report(&self) -> Result<(Dataset, ValidationReport), DatasetError> {
+        let source = DatasetSource::find(&self.dataset)
+            .ok_or_else(|| DatasetError::UnknownSource(self.dataset.clone()))?;
+        // Only what the config says, so a saved config loads the same rows
+        // whatever the environment
+        let mut loader = DatasetLoader::new()
+            .source(source)
+            .download()
+            .validation(self.validation);
+        if let Some(path) = &self.dataset_path {
+            loader = loader.path(path);
+        }
+        if let Some(format) = &self.dataset_format {
+            loader = loader.format(format.parse()?);
+        }
+        let (dataset, report) = loader.load_with_report()?;
+
+        Ok((dataset.filter(&self.filter)?, report))
+    }
+
+    /// Splits `dataset` into train, validation and test partitions.
+    pub fn split_dataset(&self, dataset: &Dataset) -> Result<[Dataset; 3], DatasetError> {
+        dataset.split_with(self.split_ratios, self.split_seed, self.split)
+    }
+}
+
+/// Parses the options listed in [`MODEL_USAGE`] and returns the artifact
+/// directory.
+pub fn artifact_dir_from_args(mut args: impl Iterator<Item = String>) -> Result<String, String> {
+    let mut artifact_dir = crate::ARTIFACT_DIR.to_string();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--artifact-dir" => {
+                artifact_dir = args.next().ok_or(format!("Missing value for {arg}"))?;
+            }
+            _ => return Err(format!("Unknown option {arg}")),
+        }
+    }
+
+    Ok(artifact_dir)
+}
+
+fn parse<T: FromStr>(arg: &str, value: String) -> Result<T, String>
+where
+    T::Err: Display,
+{
+    value
+        .parse()
+        .map_err(|e| format!("Invalid value for {arg}: {e}"))
+}
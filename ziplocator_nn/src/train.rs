@@ -1,6 +1,8 @@
-use std::{io::Write, time::Duration};
+use std::{io::Write, path::Path, time::Duration};
 
 use burn::{
+    config::Config,
+    config::ConfigError,
     lr_scheduler::exponential::ExponentialLrSchedulerConfig,
     module::Module,
     optim::AdamConfig,
@@ -12,6 +14,125 @@ use burn::{
         LearnerBuilder, RegressionOutput, TrainOutput, TrainStep, ValidStep,
     },
 };
+use ziplocator_data::{
+    projection::Projection, Dataset, DatasetFilter, DatasetSource, SplitStrategy, ValidationPolicy,
+};
+
+use crate::TrainingError;
+
+/// Everything that determines a training run, including which zips it trains
+/// on. It is saved next to the model, so a run can be repeated with
+/// `--config <artifact dir>/training_config.json` and evaluated on the same
+/// test zips.
+#[derive(Config)]
+pub struct TrainingConfig {
+    #[config(default = 1000)]
+    pub num_epochs: usize,
+    #[config(default = 100)]
+    pub batch_size: usize,
+    #[config(default = "AdamConfig::new()")]
+    pub optimizer: AdamConfig,
+    /// Start and per-step decay of the exponential learning rate schedule
+    #[config(default = 0.01)]
+    pub learning_rate: f64,
+    #[config(default = 0.9999)]
+    pub learning_rate_decay: f64,
+    #[config(default = "Projection::LatLon")]
+    pub projection: Projection,
+    #[config(default = "crate::Sampling::Uniform")]
+    pub sampling: crate::Sampling,
+    /// Seeds the backend and the data loaders
+    #[config(default = 42)]
+    pub seed: u64,
+    /// Name of the [`DatasetSource`] the dataset comes from
+    #[config(default = "DatasetSource::default().name.into()")]
+    pub dataset: String,
+    /// Local copy read instead of downloading the source, see
+    /// [`ziplocator_data::DatasetLoader::path`]
+    #[config(default = "None")]
+    pub dataset_path: Option<String>,
+    /// Format of an unverified `dataset_path`, e.g. `census`
+    #[config(default = "None")]
+    pub dataset_format: Option<String>,
+    /// What happens to rows failing validation, before filtering
+    #[config(default = "ValidationPolicy::Drop")]
+    pub validation: ValidationPolicy,
+    #[config(default = "DatasetFilter::new()")]
+    pub filter: DatasetFilter,
+    #[config(default = "SplitStrategy::default()")]
+    pub split: SplitStrategy,
+    /// Train, validation and test shares of the filtered dataset
+    #[config(default = "crate::SPLIT_RATIOS")]
+    pub split_ratios: [f64; 3],
+    #[config(default = "crate::SPLIT_SEED")]
+    pub split_seed: u64,
+    #[config(default = "crate::ARTIFACT_DIR.into()")]
+    pub artifact_dir: String,
+}
+
+// The optimizer config has no `Debug`, so the JSON form stands in for it
+impl std::fmt::Debug for TrainingConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TrainingConfig {self}")
+    }
+}
+
+impl Default for TrainingConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TrainingConfig {
+    /// Reads a TOML file if the path ends in `.toml`, JSON otherwise. Fields
+    /// missing from the file keep their defaults, also inside nested tables
+    /// such as `optimizer`.
+    pub fn load_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|_| ConfigError::FileNotFound(path.display().to_string()))?;
+        let invalid = |error: &dyn std::fmt::Display| ConfigError::InvalidFormat(error.to_string());
+        let file: serde_json::Value = if path
+            .extension()
+            .is_some_and(|extension| extension == "toml")
+        {
+            toml::from_str(&content).map_err(|error| invalid(&error))?
+        } else {
+            serde_json::from_str(&content).map_err(|error| invalid(&error))?
+        };
+
+        let mut config = serde_json::to_value(Self::new()).map_err(|error| invalid(&error))?;
+        overlay(&mut config, file);
+        serde_json::from_value(config).map_err(|error| invalid(&error))
+    }
+
+    /// The config a model in `artifact_dir` was trained with, pointing at
+    /// that directory even if it was moved since.
+    pub fn from_artifact_dir(artifact_dir: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let artifact_dir = artifact_dir.as_ref();
+        let config = Self::load_file(artifact_dir.join(crate::TRAINING_CONFIG_FILE))?;
+
+        Ok(config.with_artifact_dir(artifact_dir.display().to_string()))
+    }
+}
+
+/// Replaces the values in `base` with those in `overlay`, merging objects key
+/// by key.
+fn overlay(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(base) => self::overlay(base, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
 
 impl<B: AutodiffBackend> TrainStep<crate::ZipBatch<B>, RegressionOutput<B>> for crate::ZipModel<B> {
     fn step(&self, item: crate::ZipBatch<B>) -> burn::train::TrainOutput<RegressionOutput<B>> {
@@ -26,35 +147,71 @@ impl<B: Backend> ValidStep<crate::ZipBatch<B>, RegressionOutput<B>> for crate::Z
     }
 }
 
-pub fn train<B: AutodiffBackend>(device: &B::Device) {
-    let model = crate::ZipModel::<B>::new(device);
-    let optimizer = AdamConfig::new().init();
-    let lr_scheduler = ExponentialLrSchedulerConfig::new(0.01, 0.9999)
-        .init()
-        .unwrap();
+/// Trains on the train and validation partitions of `dataset`, which should
+/// be loaded with [`TrainingConfig::load_dataset`].
+pub fn train<B: AutodiffBackend>(
+    device: &B::Device,
+    dataset: &Dataset,
+    training: &TrainingConfig,
+) -> Result<(), TrainingError> {
+    B::seed(training.seed);
+
+    let optimizer = training.optimizer.init();
+    let lr_scheduler =
+        ExponentialLrSchedulerConfig::new(training.learning_rate, training.learning_rate_decay)
+            .init()
+            .map_err(TrainingError::InvalidConfig)?;
 
-    let loader_train = crate::create_loader(device);
-    let loader_valid = crate::create_loader(device);
+    // Normalized by the training partition alone, so nothing about the
+    // held-out zips leaks into the model
+    let [train, valid, _test] = training.split_dataset(dataset)?;
+    let config = crate::ZipModelConfig::from_dataset(&train, training.projection)?;
+    let model = config.init::<B>(device);
 
-    let learner = LearnerBuilder::<B, _, _, _, _, _>::new(crate::ARTIFACT_DIR)
+    let loader_train = crate::create_loader(device, &train, config.input_size, training)?;
+    // Validation loss stays comparable between runs with different sampling
+    let loader_valid = crate::create_loader(
+        device,
+        &valid,
+        config.input_size,
+        &training.clone().with_sampling(crate::Sampling::Uniform),
+    )?;
+
+    // Saved up front, so an interrupted run can still be repeated
+    let artifact_dir = Path::new(&training.artifact_dir);
+    std::fs::create_dir_all(artifact_dir)
+        .map_err(TrainingError::saving(artifact_dir.to_path_buf()))?;
+    let model_config_file = artifact_dir.join(crate::MODEL_CONFIG_FILE);
+    config
+        .save(&model_config_file)
+        .map_err(TrainingError::saving(model_config_file))?;
+    let training_config_file = artifact_dir.join(crate::TRAINING_CONFIG_FILE);
+    training
+        .save(&training_config_file)
+        .map_err(TrainingError::saving(training_config_file))?;
+
+    let learner = LearnerBuilder::<B, _, _, _, _, _>::new(&training.artifact_dir)
         .metric_train_numeric(LossMetric::new())
         .metric_valid_numeric(LossMetric::new())
         .metric_train(LearningRateMetric::new())
-        .num_epochs(1000)
+        .num_epochs(training.num_epochs)
         .devices(vec![device.clone()])
         .build(model, optimizer, lr_scheduler);
 
     let model = learner.fit(loader_train, loader_valid);
 
+    let model_file = artifact_dir.join(crate::MODEL_FILE);
     model
         .save_file(
-            format!("{}{}", crate::ARTIFACT_DIR, crate::MODEL_FILE),
+            &model_file,
             &PrettyJsonFileRecorder::<FullPrecisionSettings>::new(),
         )
-        .expect("Unable to save model");
+        .map_err(TrainingError::saving(model_file))?;
 
     println!("Model saved!");
 
     std::io::stdout().flush().ok();
     std::thread::sleep(Duration::from_millis(100));
+
+    Ok(())
 }
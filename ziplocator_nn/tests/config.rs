@@ -0,0 +1,127 @@
+use burn::{config::Config, optim::AdamConfig};
+use ziplocator_data::{Dataset, DatasetFilter, SplitStrategy, ZipCode, ZipGroup};
+use ziplocator_nn::TrainingConfig;
+
+#[test]
+fn saved_config_reproduces_the_test_split() {
+    let artifact_dir = std::env::temp_dir().join("ziplocator_saved_config_test");
+    std::fs::create_dir_all(&artifact_dir).unwrap();
+
+    let training = TrainingConfig::from_args(
+        ["--states", "NY,PA,CA", "--split", "holdout-prefix"]
+            .into_iter()
+            .map(String::from),
+    )
+    .unwrap()
+    .with_split_seed(7);
+    training
+        .save(artifact_dir.join(ziplocator_nn::TRAINING_CONFIG_FILE))
+        .unwrap();
+    let loaded = TrainingConfig::from_artifact_dir(&artifact_dir).unwrap();
+
+    assert_eq!(
+        loaded.filter,
+        DatasetFilter::new().states(["NY", "PA", "CA"])
+    );
+    assert_eq!(loaded.split, SplitStrategy::HoldOut(ZipGroup::Prefix));
+    assert_eq!(loaded.split_seed, 7);
+    assert_eq!(loaded.artifact_dir, artifact_dir.display().to_string());
+
+    let dataset = Dataset::synthetic(2_000, 5);
+    let [_, _, expected] = training
+        .split_dataset(&dataset.filter(&training.filter).unwrap())
+        .unwrap();
+    let [_, _, test] = loaded
+        .split_dataset(&dataset.filter(&loaded.filter).unwrap())
+        .unwrap();
+    let zips = |dataset: Dataset| {
+        dataset
+            .records()
+            .map(|record| record.zip)
+            .collect::<Vec<_>>()
+    };
+    let (test, expected) = (zips(test), zips(expected));
+    assert!(!test.is_empty());
+    assert_eq!(test, expected);
+
+    std::fs::remove_dir_all(artifact_dir).ok();
+}
+
+#[test]
+fn partial_config_files_keep_the_defaults() {
+    let dir = std::env::temp_dir().join("ziplocator_partial_config_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    let toml = dir.join("training.toml");
+    std::fs::write(
+        &toml,
+        "num_epochs = 50\nsplit = \"holdout-state\"\n\n[optimizer]\nbeta_1 = 0.8\n",
+    )
+    .unwrap();
+    let json = dir.join("training.json");
+    std::fs::write(&json, r#"{"batch_size": 20, "filter": {"states": ["CA"]}}"#).unwrap();
+
+    let defaults = TrainingConfig::new();
+    let from_toml = TrainingConfig::load_file(&toml).unwrap();
+    assert_eq!(from_toml.num_epochs, 50);
+    assert_eq!(from_toml.split, SplitStrategy::HoldOut(ZipGroup::State));
+    assert_eq!(
+        from_toml.optimizer.to_string(),
+        AdamConfig::new().with_beta_1(0.8).to_string()
+    );
+    assert_eq!(from_toml.batch_size, defaults.batch_size);
+    assert_eq!(from_toml.split_ratios, defaults.split_ratios);
+
+    let from_json = TrainingConfig::load_file(&json).unwrap();
+    assert_eq!(from_json.batch_size, 20);
+    assert_eq!(from_json.num_epochs, defaults.num_epochs);
+    assert_eq!(from_json.filter, DatasetFilter::new().states(["CA"]));
+
+    std::fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn saved_config_reloads_the_same_local_dataset() {
+    let dir = std::env::temp_dir().join("ziplocator_dataset_config_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("geonames.txt");
+    std::fs::write(
+        &path,
+        "US\t10001\tNew York\tNew York\tNY\tNew York County\t061\t\t\t40.7484\t-73.9967\t4
+US\t94103\tSan Francisco\tCalifornia\tCA\tSan Francisco County\t075\t\t\t37.7725\t-122.4147\t4
+",
+    )
+    .unwrap();
+
+    let training = TrainingConfig::from_args(
+        [
+            "--dataset-path",
+            path.to_str().unwrap(),
+            "--dataset-format",
+            "geonames",
+            "--states",
+            "CA",
+        ]
+        .into_iter()
+        .map(String::from),
+    )
+    .unwrap()
+    .with_artifact_dir(dir.display().to_string());
+    training
+        .save(dir.join(ziplocator_nn::TRAINING_CONFIG_FILE))
+        .unwrap();
+    let loaded = TrainingConfig::from_artifact_dir(&dir).unwrap();
+    assert_eq!(loaded.dataset, training.dataset);
+    assert_eq!(loaded.dataset_format.as_deref(), Some("geonames"));
+
+    let dataset = loaded.load_dataset().unwrap();
+    let zips = dataset
+        .records()
+        .map(|record| record.zip)
+        .collect::<Vec<_>>();
+    assert_eq!(zips, [ZipCode::new(94103).unwrap()]);
+
+    let unknown = loaded.with_dataset("uszips-0.1".into());
+    assert!(unknown.load_dataset().is_err());
+
+    std::fs::remove_dir_all(dir).ok();
+}
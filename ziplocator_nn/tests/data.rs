@@ -0,0 +1,49 @@
+use burn::{
+    backend::{ndarray::NdArrayDevice, NdArray},
+    data::dataloader::batcher::Batcher,
+};
+use ziplocator_data::{projection::Projection, Dataset};
+use ziplocator_nn::{create_loader, Sampling, TrainingConfig, ZipBatcher, ZipItem};
+
+#[test]
+fn batcher_encodes_zips_and_locations() {
+    let batcher = ZipBatcher::<NdArray> {
+        device: NdArrayDevice::Cpu,
+        input_size: 17,
+        projection: Projection::LatLon,
+    };
+    let items = Dataset::synthetic(10, 3)
+        .records()
+        .map(|record| ZipItem {
+            zip: record.zip,
+            latitude: record.latitude,
+            longitude: record.longitude,
+        })
+        .collect();
+
+    let batch = batcher.batch(items);
+    assert_eq!(batch.zips.dims(), [10, 17]);
+    assert_eq!(batch.locations.dims(), [10, 2]);
+}
+
+#[test]
+fn loader_batches_have_expected_shapes() {
+    let dataset = Dataset::synthetic(250, 3);
+
+    for (projection, sampling) in [
+        (Projection::LatLon, Sampling::Uniform),
+        (Projection::Ecef, Sampling::Population),
+    ] {
+        let training = TrainingConfig::new()
+            .with_projection(projection)
+            .with_sampling(sampling);
+        let loader =
+            create_loader::<NdArray>(&NdArrayDevice::Cpu, &dataset, 17, &training).unwrap();
+
+        let sizes: Vec<_> = loader.iter().map(|batch| batch.zips.dims()).collect();
+        assert_eq!(sizes, [[100, 17], [100, 17], [50, 17]]);
+
+        let batch = loader.iter().next().unwrap();
+        assert_eq!(batch.locations.dims(), [100, projection.dimensions()]);
+    }
+}
@@ -0,0 +1,22 @@
+use ziplocator_data::{projection::Projection, Dataset, DatasetFilter, ZipCode};
+use ziplocator_nn::{TrainingConfig, ZipModelConfig};
+
+#[test]
+fn normalized_by_training_partition_and_sized_for_every_zip() {
+    let massachusetts = Dataset::synthetic(2_000, 5)
+        .filter(&DatasetFilter::new().states(["MA"]))
+        .unwrap();
+    assert!(massachusetts.summary().unwrap().zip_bits < ZipCode::BITS);
+
+    let [train, _, _] = TrainingConfig::new().split_dataset(&massachusetts).unwrap();
+    let config = ZipModelConfig::from_dataset(&train, Projection::LatLon).unwrap();
+    assert_eq!(config.input_size, ZipCode::BITS as usize);
+
+    let (summary, everything) = (train.summary().unwrap(), massachusetts.summary().unwrap());
+    assert_eq!(config.output_mean, [summary.lat_mean, summary.lng_mean]);
+    assert_eq!(config.output_std, [summary.lat_std, summary.lng_std]);
+    assert_ne!(
+        config.output_mean,
+        [everything.lat_mean, everything.lng_mean]
+    );
+}
@@ -14,10 +14,13 @@ use crate::map::worker::{MapCommand, MapWorker};
 
 pub struct State {
     inferrer: Box<dyn ziplocator_nn::Inferrer>,
-    dataset: ziplocator_data::Dataset,
+    dataset: Result<ziplocator_data::Dataset, ziplocator_data::DatasetError>,
     map_controller: Option<mpsc::Sender<MapCommand>>,
     map_frame: Option<ImageHandle>,
     zip_code: String,
+    place_name: Option<String>,
+    prediction_error_km: Option<f64>,
+    neighbors: Vec<ziplocator_data::ZipCode>,
     legend_visible: bool,
     debug_enabled: bool,
     nn_layers: debug::Layers,
@@ -31,6 +34,9 @@ impl Default for State {
             map_controller: None,
             map_frame: None,
             zip_code: "".into(),
+            place_name: None,
+            prediction_error_km: None,
+            neighbors: Vec::new(),
             legend_visible: false,
             debug_enabled: false,
             nn_layers: Vec::new(),
@@ -55,7 +61,7 @@ fn view(state: &State) -> Element<Message> {
             widget::text_input("Enter zip code...", &state.zip_code)
                 .style(|theme, status| {
                     let mut style = widget::text_input::default(theme, status);
-                    if let Err(_) = state.zip_code.parse::<u32>() {
+                    if let Err(_) = state.zip_code.parse::<ziplocator_data::ZipCode>() {
                         style.border.color = theme.palette().danger;
                     }
                     style
@@ -67,7 +73,11 @@ fn view(state: &State) -> Element<Message> {
                 .style(widget::button::primary)
                 .on_press(Message::RunPrediction),
             widget::horizontal_space(),
-            widget::text!("Enter a zip code or right click the map")
+            match &state.dataset {
+                Ok(_) => widget::text!("Enter a zip code or right click the map"),
+                Err(error) =>
+                    widget::text!("Dataset unavailable: {error}").style(widget::text::danger),
+            }
         ]
         .align_y(Alignment::Center)
         .spacing(10),
@@ -84,6 +94,13 @@ fn view(state: &State) -> Element<Message> {
                 widget::right(
                     widget::container(
                         widget::column![
+                            widget::text(state.place_name.as_deref().unwrap_or("Unknown zip code")),
+                            widget::text(match state.prediction_error_km {
+                                Some(error_km) => format!("Off by {error_km:.1} km"),
+                                None => "Not in dataset".into(),
+                            }),
+                            widget::text(neighbors_label(&state.neighbors)),
+                            widget::horizontal_rule(10),
                             widget::text!("ʘ Prediction").color(iced::color!(0xFF0000)),
                             widget::text!("ʘ Dataset").color(iced::color!(0x0000FF)),
                             widget::horizontal_rule(10),
@@ -143,6 +160,16 @@ fn view(state: &State) -> Element<Message> {
     widget::column![controls, map].into()
 }
 
+fn neighbors_label(neighbors: &[ziplocator_data::ZipCode]) -> String {
+    match neighbors {
+        [] => "No neighbouring zips".into(),
+        neighbors => {
+            let zips: Vec<_> = neighbors.iter().map(ToString::to_string).collect();
+            format!("Neighbours: {}", zips.join(", "))
+        }
+    }
+}
+
 fn update(state: &mut State, message: Message) {
     match message {
         Message::SetMapController(controller) => state.map_controller = Some(controller),
@@ -153,7 +180,12 @@ fn update(state: &mut State, message: Message) {
                 height,
             } => state.map_frame = Some(widget::image::Handle::from_rgba(width, height, png_data)),
             MapMessage::LocationClicked(geo) => {
-                if let Some(zip) = state.dataset.nearest_zip(geo.lat(), geo.lon()) {
+                let nearest_zip = state
+                    .dataset
+                    .as_ref()
+                    .ok()
+                    .and_then(|dataset| dataset.zip_at(geo.lat(), geo.lon()));
+                if let Some(zip) = nearest_zip {
                     state.zip_code = zip.to_string();
                     update(state, Message::RunPrediction);
                 }
@@ -169,10 +201,14 @@ fn update(state: &mut State, message: Message) {
             let mut recorder = ziplocator_nn::LayerOutputRecorder::default();
 
             let prediction = state.inferrer.infer(zip, Some(&mut recorder));
-            let dataset = state
+            let record = state
                 .dataset
-                .zip_location(zip)
-                .map(|(lat, lon)| latlon!(lat, lon));
+                .as_ref()
+                .ok()
+                .and_then(|dataset| dataset.record(zip));
+            let dataset = record
+                .as_ref()
+                .map(|record| latlon!(record.latitude, record.longitude));
 
             map_controller
                 .try_send(MapCommand::PlacePins {
@@ -181,6 +217,18 @@ fn update(state: &mut State, message: Message) {
                 })
                 .ok();
 
+            state.prediction_error_km = record.as_ref().map(|record| {
+                ziplocator_data::geo::haversine_distance(
+                    (record.latitude, record.longitude),
+                    (prediction.latitude, prediction.longitude),
+                )
+            });
+            state.neighbors = state
+                .dataset
+                .as_ref()
+                .map(|dataset| dataset.neighbors(zip))
+                .unwrap_or_default();
+            state.place_name = record.map(|record| format!("{}, {}", record.city, record.state_id));
             state.legend_visible = true;
             state.nn_layers = recorder.layers;
         }